@@ -1,6 +1,6 @@
 use nalgebra::{Point2, Vector2};
 use pg_sdl::prelude::*;
-use pg_sdl::widgets::{switch::Switch, text_input::TextInput, Widgets};
+use pg_sdl::widgets::{switch::Switch, text_input::TextInput, Tooltip, Widgets};
 
 // Here we define our app-state struct
 pub struct MyApp {
@@ -15,7 +15,7 @@ impl App for MyApp {
 	fn update(&mut self, _delta: f64, input: &Input, widgets: &mut Widgets) -> bool {
 		let mut changed = false;
 		// if widgets_not_changed { TODO implement that <-
-		changed |= self.camera.update(input);
+		changed |= self.camera.update(input, false);
 		// }
 
 		if self.draw_circle {
@@ -27,6 +27,7 @@ impl App for MyApp {
 			self.draw_circle = true;
 			changed = true;
 		}
+		widgets.get_mut::<Spinner>("spinner").unwrap().set_running(self.draw_circle);
 		changed
 	}
 
@@ -87,7 +88,7 @@ fn main() {
 	// We can add widgets to the PgSdl struct (as long as they implement the Widget trait)
 	// We will retrieve them later in the update function with the name we gave them
 	pd_sdl
-		.add_widget(
+		.add_widget_with_tooltip(
 			"button",
 			Box::new(Button::new(
 				Colors::ROYAL_BLUE,
@@ -96,6 +97,7 @@ fn main() {
 				TextStyle::default(),
 				"Auto !".to_string(),
 			)),
+			Tooltip::text("Draws a circle\nas long as it's pushed", 0.5),
 		)
 		.add_widget(
 			"slider",
@@ -106,6 +108,12 @@ fn main() {
 				SliderType::Continuous { display: None, default_value: 0.5 },
 			)),
 		)
+		.add_widget(
+			"progress bar",
+			Box::new(ProgressBar::new(rect!(110, 260, 200, 20), None, Progress::Determinate(0.5))),
+		)
+		.add_widget("loading bar", Box::new(ProgressBar::new(rect!(330, 220, 20, 100), None, Progress::Indeterminate)))
+		.add_widget("spinner", Box::new(Spinner::new(rect!(720, 520, 60, 60), None, false)))
 		.add_widget(
 			"text input",
 			Box::new(TextInput::new(
@@ -123,6 +131,13 @@ fn main() {
 			Box::new(Switch::new(Colors::LIGHT_ORANGE, paler(Colors::LIGHT_ORANGE, 0.25), rect!(280, 140, 30, 50), 10)),
 		);
 
+	// Widgets can be wired together with callbacks, called when the user interacts with them
+	pd_sdl.widgets().on_change("slider", |value, widgets| {
+		if let Some(value) = value.as_number() {
+			widgets.get_mut::<ProgressBar>("progress bar").unwrap().set_value(value as f32);
+		}
+	});
+
 	// Finally we run the app, that take a mutable reference to our custom app-state struct
 	pd_sdl.run(&mut my_app);
 }
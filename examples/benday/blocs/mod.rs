@@ -127,6 +127,10 @@ impl Skeleton {
 		&self.size
 	}
 
+	pub fn set_color(&mut self, color: Color) {
+		self.color = color;
+	}
+
 	/// Returns a vec of the bloc's childs ids from leaf to root (including itself)
 	pub fn get_recursive_childs(&self, blocs: &HashMap<u32, Box<dyn Bloc>>) -> Vec<u32> {
 		let mut childs = Vec::new();
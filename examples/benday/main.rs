@@ -7,14 +7,17 @@ use nalgebra::{Point2, Vector2};
 use pg_sdl::app::{App, PgSdl};
 use pg_sdl::camera::Camera;
 use pg_sdl::color::{hsv_color, Colors};
-use pg_sdl::input::Input;
+use pg_sdl::input::{ActionBinding, Input, MouseCursor};
 use pg_sdl::rect;
 use pg_sdl::text::{TextDrawer, TextStyle};
-use pg_sdl::widgets::{Button, TextInput, Widgets};
+use pg_sdl::widgets::{Button, ColorPicker, Dialog, DialogResult, TextArea, TextInput, Validator, Widgets};
+use sdl2::keyboard::Keycode;
+use sdl2::pixels::Color;
 use sdl2::render::Canvas;
 use sdl2::ttf::FontStyle;
 use sdl2::video::Window;
 use std::collections::HashMap;
+use std::ops::Range;
 
 #[derive(PartialEq, Copy, Clone, Debug)]
 struct Element {
@@ -41,23 +44,83 @@ pub struct MyApp {
 	blocs_order: Vec<u32>,
 }
 
+impl MyApp {
+	/// Deletes a bloc and all its childs
+	fn delete_bloc(&mut self, bloc_id: u32) {
+		let childs = self.blocs.get(&bloc_id).unwrap().get_skeleton().get_recursive_childs(&self.blocs);
+		if let Some(Container { bloc_id: parent_id, bloc_container }) =
+			self.blocs.get(&bloc_id).unwrap().get_skeleton().get_parent().clone()
+		{
+			if let BlocContainer::Slot { slot_id } = bloc_container {
+				self.blocs.get_mut(&parent_id).unwrap().get_skeleton_mut().set_slot_empty(slot_id);
+			}
+			let root_id = get_root(&parent_id, &self.blocs);
+			update_layout_and_positions(&root_id, &mut self.blocs);
+		}
+		childs.iter().for_each(|child_id| {
+			self.blocs.remove(child_id);
+			self.blocs_order.retain(|id| id != child_id);
+		});
+	}
+}
+
 impl App for MyApp {
 	fn update(&mut self, _delta: f64, input: &Input, widgets: &mut Widgets) -> bool {
 		let mut changed = false;
 
+		// Delete the selected bloc once the user confirmed it
+		if let Some(DialogResult::Confirmed) = widgets.get_mut_dialog("Delete dialog").take_result() {
+			if let AppState::Idle { selected_element: Some(Element { bloc_id, .. }), .. } = self.app_state {
+				self.delete_bloc(bloc_id);
+				self.app_state = AppState::Idle { selected_element: None, hovered_element: None };
+				return true;
+			}
+		}
+		if widgets.is_modal_open() {
+			return false;
+		}
+
+		// Load the source files dropped on the window
+		if let Some(path) = input.dropped_files.last() {
+			if let Ok(source) = std::fs::read_to_string(path) {
+				widgets.get_mut::<TextArea>("Source").unwrap().set_text(&source);
+				changed = true;
+			}
+		}
+
 		match &self.app_state {
 			AppState::Idle { selected_element, hovered_element } => {
-				changed |= self.camera.update(input, selected_element.is_some());
+				let dragging = widgets.drag_drop().is_dragging();
+				changed |= self.camera.update(input, selected_element.is_some() || dragging);
+
+				// Paint the blocs with the colors dragged from the color picker
+				if let Some(color) = widgets.drag_drop_mut().take_drop::<Color>("bloc") {
+					if let Some(Element { bloc_id, .. }) = hovered_element {
+						self.blocs.get_mut(bloc_id).unwrap().get_skeleton_mut().set_color(color);
+						changed = true;
+					}
+				}
+				if let Some(Element { bloc_element: BlocElement::Body, .. }) = hovered_element {
+					let accepted = widgets.drag_drop().get_payload::<Color>().is_some();
+					widgets.drag_drop_mut().hover("bloc", accepted);
+				}
 
 				// Add new bloc
 				if widgets.get_button("Add").state.is_pressed() {
 					let id = self.id_counter;
-					let new_bloc =
-						Print::new(id, hsv_color((id * 15) as u16, 1.0, 1.0), Point2::new(8.0, 10.0) * id as f64);
+					let color = widgets.get::<ColorPicker>("Color").unwrap().get_color();
+					let new_bloc = Print::new(id, color, Point2::new(8.0, 10.0) * id as f64);
 					self.blocs.insert(id, Box::new(new_bloc));
 					self.blocs_order.push(id);
 					self.id_counter += 1;
 				}
+				// Ask for confirmation before deleting the selected bloc
+				else if widgets.get_button("Delete").state.is_pressed() || input.action("delete bloc").is_pressed() {
+					if selected_element.is_some() {
+						widgets.get_mut_dialog("Delete dialog").open();
+						changed = true;
+					}
+				}
 				// Mouse click
 				else if input.mouse.left_button.is_pressed() {
 					if let Some(Element { bloc_id, bloc_element }) = hovered_element {
@@ -223,6 +286,28 @@ impl App for MyApp {
 				}
 			}
 		}
+
+		// Only a selected bloc can be deleted
+		let selected = matches!(self.app_state, AppState::Idle { selected_element: Some(_), .. });
+		if widgets.is_enabled("Delete") != selected {
+			widgets.set_enabled("Delete", selected);
+			changed = true;
+		}
+
+		// Grab the blocs by their body, write in their slots, click their buttons
+		let cursor = match &self.app_state {
+			AppState::Idle { hovered_element: Some(Element { bloc_element, .. }), .. } => match bloc_element {
+				BlocElement::Body => Some(MouseCursor::Grab),
+				BlocElement::Slot(_) => Some(MouseCursor::IBeam),
+				BlocElement::Sequence(_) => None,
+				_ => Some(MouseCursor::Hand),
+			},
+			AppState::Idle { .. } => self.camera.get_cursor(),
+			AppState::BlocMoving { .. } => Some(MouseCursor::Grabbing),
+		};
+		if let Some(cursor) = cursor {
+			widgets.set_cursor(cursor);
+		}
 		changed
 	}
 
@@ -311,12 +396,64 @@ fn main() {
 			"New bloc".to_string(),
 		)),
 	);
-	app.add_widget("test", Box::new(TextInput::new(rect!(400, 100, 100, 30), None, Some("bob".to_string()))));
-	app.change_mouse_cursor();
+	app.add_widget(
+		"Delete",
+		Box::new(Button::new(
+			Colors::LIGHT_RED,
+			rect!(100, 220, 200, 50),
+			Some(9),
+			TextStyle::new(20, None, Colors::BLACK, FontStyle::NORMAL),
+			"Delete bloc".to_string(),
+		)),
+	);
+	app.add_widget("Color", Box::new(ColorPicker::new(rect!(1000, 20, 260, 290), hsv_color(330, 0.3, 1.0))));
+	app.add_widget(
+		"Delete dialog",
+		Box::new(Dialog::confirm(
+			rect!(resolution.x / 2 - 180, resolution.y / 2 - 70, 360, 140),
+			"Delete",
+			"Delete the selected bloc and its childs ?",
+		)),
+	);
+	let mut source = TextArea::new(rect!(1000, 330, 260, 370), None, "// Benday source\nlet x = 5\nprint(x)");
+	source.set_highlighter(Some(Box::new(highlight_source)));
+	app.add_widget("Source", Box::new(source));
+	let mut name_input = TextInput::new(rect!(400, 100, 100, 30), None, None);
+	name_input.set_placeholder(Some("Name"));
+	name_input.set_validator(Some(Validator::max_length(12)));
+	app.add_widget("test", Box::new(name_input));
+	app.input().action_map_mut().bind("delete bloc", ActionBinding::Key(Keycode::Delete));
 
 	app.run(my_app);
 }
 
+/// Colors the keywords, the numbers and the comments of a line of Benday source code
+fn highlight_source(line: &str) -> Vec<(Range<usize>, Color)> {
+	const KEYWORDS: [&str; 6] = ["if", "else", "while", "print", "let", "return"];
+	let chars: Vec<char> = line.chars().collect();
+	let mut spans = Vec::new();
+	let mut start = 0;
+	while start < chars.len() {
+		if chars[start] == '/' && chars.get(start + 1) == Some(&'/') {
+			spans.push((start..chars.len(), Colors::GREY));
+			break;
+		}
+		let end = if chars[start].is_alphanumeric() {
+			(start..chars.len()).find(|index| !chars[*index].is_alphanumeric()).unwrap_or(chars.len())
+		} else {
+			start + 1
+		};
+		let word: String = chars[start..end].iter().collect();
+		if KEYWORDS.contains(&word.as_str()) {
+			spans.push((start..end, Colors::DARK_VIOLET));
+		} else if word.chars().all(|c| c.is_ascii_digit()) {
+			spans.push((start..end, Colors::ORANGE));
+		}
+		start = end;
+	}
+	spans
+}
+
 fn update_layout(bloc_id: u32, blocs: &mut HashMap<u32, Box<dyn Bloc>>) {
 	let mut bloc = blocs.remove(&bloc_id).unwrap();
 	bloc.get_skeleton_mut().update_layout(&blocs);
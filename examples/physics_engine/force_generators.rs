@@ -58,6 +58,9 @@ impl Spring {
 	) -> Self {
 		Self { rest_length: default_length, end1_index, end2_index, k, b, diameter, color }
 	}
+	pub fn set_k(&mut self, k: f64) {
+		self.k = k;
+	}
 	pub fn set_end2_index(&mut self, end2_index: usize) {
 		self.end2_index = end2_index;
 	}
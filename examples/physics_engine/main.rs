@@ -12,7 +12,7 @@ use ndarray::{Array1, Array2};
 use particle::Particle;
 use pg_sdl::prelude::*;
 use pg_sdl::widgets::switch::Switch;
-use pg_sdl::widgets::Widgets;
+use pg_sdl::widgets::{Tooltip, Widgets};
 use sdl2::ttf::FontStyle;
 use std::collections::HashMap;
 
@@ -27,7 +27,11 @@ pub struct PhysicsApp {
 	force_generators: Vec<Box<dyn ForceGenerator>>,
 	last_lambda: Option<Array1<f64>>,
 	mouse_spring: Spring,
-	draw_forces: bool,
+	/// Values shared with the widgets
+	speed: Binding<f32>,
+	draw_forces: Binding<bool>,
+	mouse_spring_k: Binding<f64>,
+	mass: Binding<f64>,
 }
 
 impl PhysicsApp {
@@ -57,14 +61,25 @@ impl PhysicsApp {
 			force_generators,
 			last_lambda: None,
 			mouse_spring,
-			draw_forces: false,
+			speed: Binding::new(0.0),
+			draw_forces: Binding::new(false),
+			mouse_spring_k: Binding::new(100.0),
+			mass: Binding::new(1.0),
 		}
 	}
 
 	fn manage_input(&mut self, input: &Input, widgets: &mut Widgets) {
-		if widgets.get_button("reset").state.is_pressed() {
+		if input.binding_pressed("reset") {
+			widgets.get_mut_dialog("reset dialog").open();
+		}
+		if let Some(DialogResult::Confirmed) = widgets.get_mut_dialog("reset dialog").take_result() {
 			self.particles = self.original_particles.iter().map(|particle| particle.clone()).collect();
 			self.constrains.iter_mut().for_each(|constrain| constrain.init(&self.particles));
+			// Pauses the simulation, the slider follows
+			self.speed.set(0.0);
+		}
+		if widgets.is_modal_open() {
+			return;
 		}
 
 		if input.mouse.left_button.is_pressed() {
@@ -194,9 +209,11 @@ impl App for PhysicsApp {
 		let mut changed = false;
 		self.manage_input(input, widgets);
 
-		self.draw_forces = widgets.get::<Switch>("switch").unwrap().is_switched();
+		self.mouse_spring.set_k(self.mouse_spring_k.get());
+		let mass = self.mass.get();
+		self.particles.iter_mut().skip(1).for_each(|particle| particle.set_mass(mass));
 
-		let speed = widgets.get_slider("speed").get_value() as f64;
+		let speed = self.speed.get() as f64;
 		self.time += delta * speed as f64;
 
 		changed |= speed != 0.0;
@@ -210,8 +227,11 @@ impl App for PhysicsApp {
 		let mouse_velocity = input.mouse.delta.cast() / delta;
 		self.particles[0].set_velocity(velocity * 0.9 + mouse_velocity * 0.1);
 
-		if self.mouse_spring.get_end2_index() == 0 {
-			changed |= self.camera.update(input);
+		if self.mouse_spring.get_end2_index() == 0 && !widgets.is_modal_open() {
+			changed |= self.camera.update(input, false);
+			if let Some(cursor) = self.camera.get_cursor() {
+				widgets.set_cursor(cursor);
+			}
 		}
 		changed
 	}
@@ -223,7 +243,7 @@ impl App for PhysicsApp {
 		self.force_generators
 			.iter()
 			.for_each(|force_generator| force_generator.draw(canvas, &self.camera, &self.particles));
-		if self.draw_forces {
+		if self.draw_forces.get() {
 			self.particles.iter().for_each(|particle| particle.draw_forces(canvas, &self.camera, 0.05));
 		}
 		self.mouse_spring.draw(canvas, &self.camera, &self.particles);
@@ -275,15 +295,49 @@ fn main() {
 	let mut app: PgSdl = PgSdl::init("Spring test", resolution.x, resolution.y, Some(60), true, background_color);
 	let slider_type =
 		SliderType::Continuous { default_value: 0.0, display: Some(Box::new(|value| format!("{:.2}", value))) };
-	let slider = Slider::new(Colors::ORANGE, rect!(500, 50, 200, 32), 16, slider_type);
+	let mut slider = Slider::new(Colors::ORANGE, rect!(500, 50, 200, 32), 16, slider_type);
+	slider.set_binding(Some(my_app.speed.clone()));
 	let button =
 		Button::new(Colors::LIGHT_YELLOW, rect!(750, 35, 120, 50), Some(9), TextStyle::default(), "Reset".to_string());
-	let switch = Switch::new(Colors::VIOLET, Colors::DARK_VIOLET, rect!(920, 40, 25, 40), 10);
+	let mut switch = Switch::new(Colors::VIOLET, Colors::DARK_VIOLET, rect!(920, 40, 25, 40), 10);
+	switch.set_binding(Some(my_app.draw_forces.clone()));
+	let mut mouse_spring_k = NumberInput::new(
+		rect!(980, 28, 130, 28),
+		NumberType::Float { precision: 1 },
+		100.0,
+		0.0,
+		1000.0,
+		5.0,
+		Some("N/m"),
+	);
+	mouse_spring_k.set_binding(Some(my_app.mouse_spring_k.clone()));
+	let mut mass =
+		NumberInput::new(rect!(980, 64, 130, 28), NumberType::Float { precision: 2 }, 1.0, 0.1, 100.0, 0.1, Some("kg"));
+	mass.set_binding(Some(my_app.mass.clone()));
+	let reset_dialog = Dialog::confirm(
+		rect!(resolution.x / 2 - 180, resolution.y / 2 - 70, 360, 140),
+		"Reset",
+		"Reset the simulation to its initial state ?",
+	);
+	app.input().key_bindings_mut().register("reset", vec!["Ctrl+R".parse().unwrap()]);
 	app.add_widgets(HashMap::from([
 		("reset", Box::new(button) as Box<dyn Widget>),
-		("speed", Box::new(slider) as Box<dyn Widget>),
-		("switch", Box::new(switch) as Box<dyn Widget>),
+		("reset dialog", Box::new(reset_dialog) as Box<dyn Widget>),
 	]));
+	app.add_widget_with_tooltip("speed", Box::new(slider), Tooltip::text("Simulation speed", 0.5))
+		.add_widget_with_tooltip(
+			"switch",
+			Box::new(switch),
+			Tooltip::text("Shows the forces\napplied on the particles", 0.5),
+		)
+		.add_widget_with_tooltip(
+			"mouse spring k",
+			Box::new(mouse_spring_k),
+			Tooltip::text("Stiffness of the spring\npulling particles to the mouse", 0.5),
+		)
+		.add_widget_with_tooltip("mass", Box::new(mass), Tooltip::text("Mass of the particles", 0.5));
+
+	app.widgets().on_click("reset", |widgets| widgets.get_mut_dialog("reset dialog").open());
 
 	app.run(&mut my_app);
 }
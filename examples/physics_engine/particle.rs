@@ -197,6 +197,9 @@ impl Particle {
 	pub fn get_mass(&self) -> f64 {
 		self.mass
 	}
+	pub fn set_mass(&mut self, mass: f64) {
+		self.mass = mass;
+	}
 
 	pub fn apply_force(&mut self, force: Vector2<f64>) {
 		self.force_accumulator.push(force);
@@ -40,7 +40,13 @@ struct AppState {}
 
 impl App for AppState {
 	fn update(&mut self, _delta: f64, _input: &Input, widgets: &mut Widgets) -> bool {
-		if widgets.get_button("login button").state.is_pressed() {
+		// Logs in with the button or with Enter in the password input
+		let login = widgets.take_events().iter().any(|(name, event)| match event {
+			WidgetEvent::Clicked => name == "login button",
+			WidgetEvent::Submitted(_) => name == "password input",
+			_ => false,
+		});
+		if login {
 			println!(
 				"Login: {}, password: {}",
 				widgets.get::<TextInput>("login input").unwrap().content,
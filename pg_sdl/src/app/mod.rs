@@ -1,7 +1,8 @@
+use crate::input::Cursors;
 use crate::prelude::*;
-use crate::widgets::Widgets;
+use crate::widgets::{AccessBackend, Tooltip, Widgets};
 use ndarray::AssignElem;
-use sdl2::mouse::{Cursor, MouseUtil, SystemCursor};
+use sdl2::surface::Surface;
 use sdl2::ttf::FontStyle;
 use sdl2::{pixels::Color, render::Canvas, video::Window};
 use std::collections::HashMap;
@@ -13,7 +14,6 @@ pub trait App {
 }
 
 pub struct PgSdl {
-	mouse: MouseUtil,
 	input: Input,
 	canvas: Canvas<Window>,
 	text_drawer: TextDrawer,
@@ -21,6 +21,7 @@ pub struct PgSdl {
 	widgets: Widgets,
 	fps: Option<u32>,
 	draw_fps: bool,
+	cursors: Cursors,
 }
 
 impl PgSdl {
@@ -44,14 +45,14 @@ impl PgSdl {
 		let canvas = window.into_canvas().build().expect("Canvas could not be created");
 
 		PgSdl {
-			mouse: sdl_context.mouse(),
 			text_drawer: TextDrawer::new(canvas.texture_creator()),
-			input: Input::new(sdl_context, video_subsystem.clipboard()),
+			input: Input::new(sdl_context, video_subsystem.clipboard(), video_subsystem.text_input()),
 			widgets: Widgets::new(),
 			canvas,
 			background_color,
 			fps,
 			draw_fps,
+			cursors: Cursors::new(),
 		}
 	}
 
@@ -110,6 +111,7 @@ impl PgSdl {
 			if self.update(user_app, frame_time) {
 				self.draw(user_app);
 			}
+			self.cursors.set(self.widgets.get_cursor());
 
 			// FPS
 			if self.draw_fps {
@@ -131,20 +133,43 @@ impl PgSdl {
 		}
 	}
 
+	/// To register the key bindings of the app before running it
+	pub fn input(&self) -> &Input {
+		&self.input
+	}
+
+	/// To set up the widgets before running the app (focus order, callbacks...)
+	pub fn widgets(&mut self) -> &mut Widgets {
+		&mut self.widgets
+	}
+
 	pub fn add_widget(&mut self, name: &str, widget: Box<dyn Widget>) -> &mut Self {
 		self.widgets.add(name, widget);
 		self
 	}
 
+	pub fn add_widget_with_tooltip(&mut self, name: &str, widget: Box<dyn Widget>, tooltip: Tooltip) -> &mut Self {
+		self.widgets.add_with_tooltip(name, widget, tooltip);
+		self
+	}
+
 	pub fn add_widgets(&mut self, widgets: HashMap<&str, Box<dyn Widget>>) {
 		for (name, widget) in widgets {
 			self.widgets.add(name, widget);
 		}
 	}
 
-	pub fn change_mouse_cursor(&mut self) {
-		let cursor = Cursor::from_system(SystemCursor::WaitArrow).expect("mouse cursor loading error");
-		cursor.set();
-		// self.mouse
+	/// Exposes the accessibility tree of the widgets through the backend (like an AccessKit adapter)
+	pub fn set_access_backend(&mut self, backend: Box<dyn AccessBackend>) -> &mut Self {
+		self.widgets.set_access_backend(Some(backend));
+		self
+	}
+
+	/// Adds a bitmap cursor (like `Surface::load_bmp`) that the widgets and the app can set with
+	/// `MouseCursor::Custom(name)`, 'hot_spot' is the pixel of the surface at the mouse position
+	pub fn add_custom_cursor(
+		&mut self, name: &'static str, surface: Surface, hot_spot: (i32, i32),
+	) -> Result<(), String> {
+		self.cursors.add_custom(name, surface, hot_spot)
 	}
 }
@@ -1,5 +1,6 @@
 use crate::canvas::{draw_rect, draw_rounded_rect, fill_rect, fill_rounded_rect};
 use crate::color::{darker, Colors};
+use crate::input::MouseCursor;
 use crate::style::{Align, HAlign, VAlign};
 use crate::text::{TextDrawer, TextStyle};
 use crate::vector2::Vector2Plus;
@@ -22,6 +23,12 @@ pub struct Camera {
 	bottom_limit: f64,
 	left_limit: f64,
 	right_limit: f64,
+	/// Action translating the camera by the mouse movement while it's held
+	pan_action: String,
+	/// Axis zooming the camera around the mouse (one doubling step per unit)
+	zoom_axis: String,
+	/// Whether the camera was translated by the mouse this frame
+	panning: bool,
 }
 
 impl Camera {
@@ -39,9 +46,24 @@ impl Camera {
 			bottom_limit,
 			left_limit,
 			right_limit,
+			pan_action: "camera pan".to_string(),
+			zoom_axis: "camera zoom".to_string(),
+			panning: false,
 		}
 	}
 
+	/// Names of the action and axis (in the input action map) controlling the camera,
+	/// by default "camera pan" (left button drag) and "camera zoom" (wheel)
+	pub fn set_controls(&mut self, pan_action: &str, zoom_axis: &str) {
+		self.pan_action = pan_action.to_string();
+		self.zoom_axis = zoom_axis.to_string();
+	}
+
+	/// The grabbing cursor while the camera is panned with the mouse
+	pub fn get_cursor(&self) -> Option<MouseCursor> {
+		self.panning.then_some(MouseCursor::Grabbing)
+	}
+
 	fn scale(&self) -> f64 {
 		self.transform.scaling()
 	}
@@ -55,19 +77,31 @@ impl Camera {
 		camera_scope.has_intersection(rect)
 	}
 
-	/// Translates and scales the camera from the inputs
+	/// Translates and scales the camera from the inputs (mouse, actions and touch gestures),
+	/// 'lock_translation' prevents the pans
 	pub fn update(&mut self, input: &Input, lock_translation: bool) -> bool {
 		let mut changed = false;
 
-		if input.mouse.left_button.is_down() && !lock_translation {
+		// The mouse emulated from the touch screen would pan along with the touch gestures
+		self.panning = input.action(&self.pan_action).is_down() && !lock_translation && !input.mouse.from_touch;
+		if self.panning {
 			let mouse_delta = input.mouse.delta.cast();
 			changed |= self.translate(mouse_delta);
 		}
 
-		let scaling = self.scaling_factor.powf(input.mouse.wheel as f64);
+		let scaling = self.scaling_factor.powf(input.axis(&self.zoom_axis));
 		let center = input.mouse.position.coords.cast();
 		changed |= self.change_scale(scaling, center);
 
+		// Touch screen, the gestures are normalized over the window
+		let resolution = self.resolution.cast::<f64>();
+		if let Some(delta) = input.touch.pan(1).or(input.touch.pan(2)).filter(|_| !lock_translation) {
+			changed |= self.translate(delta.component_mul(&resolution));
+		}
+		if let Some((center, scaling)) = input.touch.pinch() {
+			changed |= self.change_scale(scaling, center.coords.component_mul(&resolution));
+		}
+
 		changed
 	}
 
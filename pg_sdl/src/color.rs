@@ -94,7 +94,11 @@ pub fn hsv_color(hue: u16, saturation: f32, value: f32) -> Color {
 	Color { r: ((m + r) * 255.0) as u8, g: ((m + g) * 255.0) as u8, b: ((m + b) * 255.0) as u8, a: 255 }
 }
 
-fn color_to_hsv(color: Color) -> (u16, f32, f32) {
+/// Inverse of hsv_color, returns:
+/// - hue (0-360)
+/// - saturation (0.0 - 1.0)
+/// - value (0.0 - 1.0)
+pub fn color_to_hsv(color: Color) -> (u16, f32, f32) {
 	let (r, g, b) = color.rgb();
 	let c_max = max([r, g, b]).unwrap();
 	let c_min = min([r, g, b]).unwrap();
@@ -120,6 +124,27 @@ fn color_to_hsv(color: Color) -> (u16, f32, f32) {
 	(hue as u16, saturation, value)
 }
 
+/// Hexadecimal representation of a color: "#RRGGBB", or "#RRGGBBAA" if it's not opaque
+pub fn color_to_hex(color: Color) -> String {
+	let (r, g, b, a) = color.rgba();
+	if a == 255 {
+		format!("#{:02X}{:02X}{:02X}", r, g, b)
+	} else {
+		format!("#{:02X}{:02X}{:02X}{:02X}", r, g, b, a)
+	}
+}
+
+/// Parses a color from "#RRGGBB" or "#RRGGBBAA" (the '#' is optional)
+pub fn hex_to_color(hex: &str) -> Option<Color> {
+	let hex = hex.trim().trim_start_matches('#');
+	if !(hex.len() == 6 || hex.len() == 8) || !hex.chars().all(|c| c.is_ascii_hexdigit()) {
+		return None;
+	}
+	let channel = |index: usize| u8::from_str_radix(&hex[2 * index..2 * index + 2], 16).ok();
+	let alpha = if hex.len() == 8 { channel(3)? } else { 255 };
+	Some(Color::RGBA(channel(0)?, channel(1)?, channel(2)?, alpha))
+}
+
 pub fn darker(color: Color, value_change: f32) -> Color {
 	Color::RGB(
 		(color.r as f32 * value_change) as u8,
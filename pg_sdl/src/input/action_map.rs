@@ -0,0 +1,137 @@
+use crate::input::{Input, KeyState, Shortcut};
+use sdl2::controller::{Axis, Button};
+use sdl2::keyboard::{Keycode, Scancode};
+use sdl2::mouse::MouseButton;
+use std::collections::HashMap;
+
+/// An input that can trigger an action
+#[derive(Clone, PartialEq, Debug)]
+pub enum ActionBinding {
+	/// A key, by its symbol in the keyboard layout
+	Key(Keycode),
+	/// A key, by its physical position (like WASD whatever the layout)
+	Scancode(Scancode),
+	Mouse(MouseButton),
+	/// Pressed during the frames the wheel is scrolled up
+	WheelUp,
+	/// Pressed during the frames the wheel is scrolled down
+	WheelDown,
+	Shortcut(Shortcut),
+	/// A button of any gamepad
+	GamepadButton(Button),
+}
+
+/// An input giving a value to an axis
+#[derive(Clone, PartialEq, Debug)]
+pub enum AxisBinding {
+	/// -1 while 'negative' is held, 1 while 'positive' is held (0 when both are)
+	Buttons { negative: ActionBinding, positive: ActionBinding },
+	/// Horizontal movement of the mouse since the last frame (in pixels)
+	MouseX,
+	/// Vertical movement of the mouse since the last frame (in pixels)
+	MouseY,
+	/// Wheel scroll since the last frame (positive upwards)
+	Wheel,
+	/// An axis of any gamepad, without its deadzone (between -1 and 1)
+	GamepadAxis(Axis),
+}
+
+/// Maps action names (like "pan" or "delete bloc") to the inputs triggering them,
+/// and axis names (like "zoom") to the inputs giving their value.
+///
+/// An action can have several bindings, its state is the one of its most advanced binding
+/// (it's down if one binding is down, even if another one was just pressed)
+pub struct ActionMap {
+	actions: HashMap<String, Vec<ActionBinding>>,
+	axes: HashMap<String, Vec<AxisBinding>>,
+}
+
+impl Default for ActionMap {
+	/// The actions used by the camera, and the gamepad navigation of the widgets
+	fn default() -> Self {
+		let mut action_map = Self::new();
+		action_map.bind("camera pan", ActionBinding::Mouse(MouseButton::Left));
+		action_map.bind_axis("camera zoom", AxisBinding::Wheel);
+		action_map.bind("ui up", ActionBinding::GamepadButton(Button::DPadUp));
+		action_map.bind("ui down", ActionBinding::GamepadButton(Button::DPadDown));
+		action_map.bind("ui left", ActionBinding::GamepadButton(Button::DPadLeft));
+		action_map.bind("ui right", ActionBinding::GamepadButton(Button::DPadRight));
+		action_map.bind("ui activate", ActionBinding::GamepadButton(Button::A));
+		action_map
+	}
+}
+
+impl ActionMap {
+	/// Map without any action or axis
+	pub fn new() -> Self {
+		Self { actions: HashMap::new(), axes: HashMap::new() }
+	}
+
+	/// Adds a binding to an action (creating the action if needed)
+	pub fn bind(&mut self, action: &str, binding: ActionBinding) {
+		self.actions.entry(action.to_string()).or_default().push(binding);
+	}
+
+	/// Adds a binding to an axis (creating the axis if needed)
+	pub fn bind_axis(&mut self, axis: &str, binding: AxisBinding) {
+		self.axes.entry(axis.to_string()).or_default().push(binding);
+	}
+
+	/// Removes all the bindings of an action
+	pub fn unbind(&mut self, action: &str) {
+		self.actions.remove(action);
+	}
+
+	/// Removes all the bindings of an axis
+	pub fn unbind_axis(&mut self, axis: &str) {
+		self.axes.remove(axis);
+	}
+
+	pub fn get_bindings(&self, action: &str) -> &[ActionBinding] {
+		self.actions.get(action).map_or(&[], |bindings| bindings.as_slice())
+	}
+
+	pub fn get_axis_bindings(&self, axis: &str) -> &[AxisBinding] {
+		self.axes.get(axis).map_or(&[], |bindings| bindings.as_slice())
+	}
+
+	fn binding_state(binding: &ActionBinding, input: &Input) -> KeyState {
+		let (keys_state, mouse) = (&input.keys_state, &input.mouse);
+		match binding {
+			ActionBinding::Key(keycode) => *keys_state.get_key(*keycode),
+			ActionBinding::Scancode(scancode) => *keys_state.get_scancode(*scancode),
+			ActionBinding::Mouse(button) => mouse.get_button(*button).get_state(),
+			ActionBinding::WheelUp if mouse.wheel.y > 0 => KeyState::Pressed,
+			ActionBinding::WheelDown if mouse.wheel.y < 0 => KeyState::Pressed,
+			ActionBinding::WheelUp | ActionBinding::WheelDown => KeyState::Up,
+			ActionBinding::Shortcut(shortcut) if keys_state.shortcut_pressed(shortcut) => KeyState::Pressed,
+			ActionBinding::Shortcut(_) => KeyState::Up,
+			ActionBinding::GamepadButton(button) => input.gamepads.any_button(*button),
+		}
+	}
+
+	/// State of the action, Up if it has no binding
+	pub fn get_state(&self, action: &str, input: &Input) -> KeyState {
+		KeyState::most_advanced(self.get_bindings(action).iter().map(|binding| Self::binding_state(binding, input)))
+	}
+
+	/// Value of the axis (the sum of its bindings), 0 if it has no binding
+	pub fn get_axis(&self, axis: &str, input: &Input) -> f64 {
+		self.get_axis_bindings(axis)
+			.iter()
+			.map(|binding| match binding {
+				AxisBinding::Buttons { negative, positive } => {
+					let held = |binding| {
+						let state = Self::binding_state(binding, input);
+						state.is_pressed() || state.is_down()
+					};
+					held(positive) as i32 as f64 - held(negative) as i32 as f64
+				}
+				AxisBinding::MouseX => input.mouse.delta.x as f64,
+				AxisBinding::MouseY => input.mouse.delta.y as f64,
+				AxisBinding::Wheel => input.mouse.wheel.y as f64,
+				AxisBinding::GamepadAxis(axis) => input.gamepads.any_axis(*axis),
+			})
+			.sum()
+	}
+}
@@ -0,0 +1,116 @@
+use sdl2::mouse::{Cursor, SystemCursor};
+use sdl2::surface::Surface;
+use std::collections::hash_map::Entry;
+use std::collections::HashMap;
+
+/// Shape of the mouse cursor
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub enum MouseCursor {
+	Arrow,
+	/// Over text that can be edited or selected
+	IBeam,
+	/// Over something that can be clicked
+	Hand,
+	Wait,
+	/// Arrow with a small wait indicator, while the app works in the background
+	WaitArrow,
+	Crosshair,
+	/// Left-right arrows
+	ResizeHorizontal,
+	/// Up-down arrows
+	ResizeVertical,
+	/// Top left - bottom right arrows
+	ResizeNwse,
+	/// Top right - bottom left arrows
+	ResizeNesw,
+	ResizeAll,
+	/// Over something that can be grabbed, SDL doesn't have it so it falls back to the hand
+	/// (unless a "grab" custom cursor is added)
+	Grab,
+	/// While something is dragged, SDL doesn't have it so it falls back to the four arrows
+	/// (unless a "grabbing" custom cursor is added)
+	Grabbing,
+	NotAllowed,
+	/// A bitmap cursor added with `Cursors::add_custom`
+	Custom(&'static str),
+}
+
+impl MouseCursor {
+	fn system_cursor(&self) -> SystemCursor {
+		match self {
+			MouseCursor::Arrow | MouseCursor::Custom(_) => SystemCursor::Arrow,
+			MouseCursor::IBeam => SystemCursor::IBeam,
+			MouseCursor::Hand | MouseCursor::Grab => SystemCursor::Hand,
+			MouseCursor::Wait => SystemCursor::Wait,
+			MouseCursor::WaitArrow => SystemCursor::WaitArrow,
+			MouseCursor::Crosshair => SystemCursor::Crosshair,
+			MouseCursor::ResizeHorizontal => SystemCursor::SizeWE,
+			MouseCursor::ResizeVertical => SystemCursor::SizeNS,
+			MouseCursor::ResizeNwse => SystemCursor::SizeNWSE,
+			MouseCursor::ResizeNesw => SystemCursor::SizeNESW,
+			MouseCursor::ResizeAll | MouseCursor::Grabbing => SystemCursor::SizeAll,
+			MouseCursor::NotAllowed => SystemCursor::No,
+		}
+	}
+
+	/// Name of the custom cursor replacing the system one
+	fn custom_name(&self) -> Option<&'static str> {
+		match self {
+			MouseCursor::Custom(name) => Some(name),
+			MouseCursor::Grab => Some("grab"),
+			MouseCursor::Grabbing => Some("grabbing"),
+			_ => None,
+		}
+	}
+}
+
+/// The cursors loaded from SDL, to set the cursor only when it changes
+pub struct Cursors {
+	system: HashMap<SystemCursor, Cursor>,
+	custom: HashMap<&'static str, Cursor>,
+	current: MouseCursor,
+}
+
+impl Default for Cursors {
+	fn default() -> Self {
+		Self::new()
+	}
+}
+
+impl Cursors {
+	pub fn new() -> Self {
+		Cursors { system: HashMap::new(), custom: HashMap::new(), current: MouseCursor::Arrow }
+	}
+
+	/// Adds a bitmap cursor (like `Surface::load_bmp`), 'hot_spot' is the pixel of the surface at the mouse position
+	pub fn add_custom(&mut self, name: &'static str, surface: Surface, hot_spot: (i32, i32)) -> Result<(), String> {
+		let cursor = Cursor::from_surface(surface, hot_spot.0, hot_spot.1)?;
+		self.custom.insert(name, cursor);
+		Ok(())
+	}
+
+	pub fn get_current(&self) -> MouseCursor {
+		self.current
+	}
+
+	/// Sets the cursor of the window if it's not already set
+	pub fn set(&mut self, cursor: MouseCursor) {
+		if cursor == self.current {
+			return;
+		}
+		if let Some(custom) = cursor.custom_name().and_then(|name| self.custom.get(name)) {
+			custom.set();
+		} else {
+			let system_cursor = cursor.system_cursor();
+			let loaded = match self.system.entry(system_cursor) {
+				Entry::Occupied(entry) => entry.into_mut(),
+				Entry::Vacant(entry) => match Cursor::from_system(system_cursor) {
+					Ok(loaded) => entry.insert(loaded),
+					Err(_) => return,
+				},
+			};
+			loaded.set();
+		}
+		self.current = cursor;
+	}
+}
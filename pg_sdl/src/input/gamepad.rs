@@ -0,0 +1,236 @@
+use crate::input::KeyState;
+use nalgebra::Vector2;
+use sdl2::controller::{Axis, Button, GameController};
+use sdl2::event::Event;
+use sdl2::GameControllerSubsystem;
+use std::cell::RefCell;
+use std::collections::HashMap;
+
+/// Position of an SDL axis between -1 and 1
+fn normalize_axis(value: i16) -> f64 {
+	(value as f64 / i16::MAX as f64).max(-1.0)
+}
+
+/// Removes the deadzone from an axis position, rescaled so that it still goes smoothly from 0 to 1
+fn apply_deadzone(value: f64, deadzone: f64) -> f64 {
+	if value.abs() <= deadzone {
+		0.0
+	} else {
+		value.signum() * (value.abs() - deadzone) / (1.0 - deadzone)
+	}
+}
+
+/// Removes a radial deadzone from a stick position (which doesn't snap diagonals to the axes)
+fn apply_radial_deadzone(position: Vector2<f64>, deadzone: f64) -> Vector2<f64> {
+	let length = position.norm().min(1.0);
+	if length <= deadzone {
+		Vector2::zeros()
+	} else {
+		position.normalize() * (length - deadzone) / (1.0 - deadzone)
+	}
+}
+
+/// The position the furthest from the center
+fn furthest_axis(values: impl Iterator<Item = f64>) -> f64 {
+	values.fold(0.0, |a, b| if b.abs() > a.abs() { b } else { a })
+}
+
+/// A game controller, with the state of its buttons and the position of its axes
+pub struct Gamepad {
+	/// In a RefCell to rumble from a shared input
+	controller: RefCell<GameController>,
+	buttons: HashMap<Button, KeyState>,
+	/// Raw positions, between -1 and 1 (0 and 1 for the triggers)
+	axes: HashMap<Axis, f64>,
+	deadzone: f64,
+}
+
+impl Gamepad {
+	fn new(controller: GameController, deadzone: f64) -> Self {
+		Self { controller: RefCell::new(controller), buttons: HashMap::new(), axes: HashMap::new(), deadzone }
+	}
+
+	/// The joystick instance id, which identifies the gamepad in the SDL events
+	pub fn get_id(&self) -> u32 {
+		self.controller.borrow().instance_id()
+	}
+
+	pub fn get_name(&self) -> String {
+		self.controller.borrow().name()
+	}
+
+	pub fn get_button(&self, button: Button) -> &KeyState {
+		self.buttons.get(&button).unwrap_or(&KeyState::Up)
+	}
+
+	/// Position of the axis without the deadzone, rescaled so that it still goes smoothly from 0 to 1
+	pub fn get_axis(&self, axis: Axis) -> f64 {
+		apply_deadzone(self.get_raw_axis(axis), self.deadzone)
+	}
+
+	pub fn get_raw_axis(&self, axis: Axis) -> f64 {
+		*self.axes.get(&axis).unwrap_or(&0.0)
+	}
+
+	/// Position of a stick with a radial deadzone (which doesn't snap diagonals to the axes)
+	pub fn get_stick(&self, left: bool) -> Vector2<f64> {
+		let (x, y) = if left { (Axis::LeftX, Axis::LeftY) } else { (Axis::RightX, Axis::RightY) };
+		apply_radial_deadzone(Vector2::new(self.get_raw_axis(x), self.get_raw_axis(y)), self.deadzone)
+	}
+
+	/// Makes the gamepad vibrate, the strengths are between 0 and 1.
+	///
+	/// Returns false if the gamepad can't rumble
+	pub fn rumble(&self, low_frequency: f64, high_frequency: f64, duration_ms: u32) -> bool {
+		let strength = |value: f64| (value.clamp(0.0, 1.0) * u16::MAX as f64) as u16;
+		self.controller.borrow_mut().set_rumble(strength(low_frequency), strength(high_frequency), duration_ms).is_ok()
+	}
+}
+
+/// The connected gamepads, in the order of connection.
+///
+/// The gamepads are added and removed as they are plugged, including the ones connected before the app starts.
+/// Without hardware, they can be tested with SDL's virtual joysticks (`sdl2::sys::SDL_JoystickAttachVirtual`)
+pub struct Gamepads {
+	/// None if the game controller subsystem could not be initialized
+	subsystem: Option<GameControllerSubsystem>,
+	gamepads: Vec<Gamepad>,
+	deadzone: f64,
+}
+
+impl Gamepads {
+	const DEFAULT_DEADZONE: f64 = 0.15;
+
+	pub fn new(subsystem: Option<GameControllerSubsystem>) -> Self {
+		Self { subsystem, gamepads: Vec::new(), deadzone: Self::DEFAULT_DEADZONE }
+	}
+
+	/// Should be called every frame, before the events are handled
+	pub fn update(&mut self) {
+		self.gamepads.iter_mut().for_each(|gamepad| gamepad.buttons.values_mut().for_each(|button| button.update()));
+	}
+
+	pub fn get_event(&mut self, event: &Event) {
+		match event {
+			Event::ControllerDeviceAdded { which, .. } => {
+				if let Some(controller) = self.subsystem.as_ref().and_then(|subsystem| subsystem.open(*which).ok()) {
+					// The gamepads already opened are announced again when the app starts
+					if self.get_by_id(controller.instance_id()).is_none() {
+						self.gamepads.push(Gamepad::new(controller, self.deadzone));
+					}
+				}
+			}
+			Event::ControllerDeviceRemoved { which, .. } => {
+				self.gamepads.retain(|gamepad| gamepad.get_id() != *which);
+			}
+			Event::ControllerButtonDown { which, button, .. } => {
+				if let Some(gamepad) = self.get_mut_by_id(*which) {
+					gamepad.buttons.entry(*button).or_insert(KeyState::Up).press();
+				}
+			}
+			Event::ControllerButtonUp { which, button, .. } => {
+				if let Some(gamepad) = self.get_mut_by_id(*which) {
+					gamepad.buttons.entry(*button).or_insert(KeyState::Up).release();
+				}
+			}
+			Event::ControllerAxisMotion { which, axis, value, .. } => {
+				if let Some(gamepad) = self.get_mut_by_id(*which) {
+					gamepad.axes.insert(*axis, normalize_axis(*value));
+				}
+			}
+			_ => {}
+		}
+	}
+
+	/// The gamepad at 'index' in the order of connection
+	pub fn get(&self, index: usize) -> Option<&Gamepad> {
+		self.gamepads.get(index)
+	}
+
+	pub fn get_by_id(&self, id: u32) -> Option<&Gamepad> {
+		self.gamepads.iter().find(|gamepad| gamepad.get_id() == id)
+	}
+
+	fn get_mut_by_id(&mut self, id: u32) -> Option<&mut Gamepad> {
+		self.gamepads.iter_mut().find(|gamepad| gamepad.get_id() == id)
+	}
+
+	pub fn iter(&self) -> impl Iterator<Item = &Gamepad> {
+		self.gamepads.iter()
+	}
+
+	pub fn len(&self) -> usize {
+		self.gamepads.len()
+	}
+
+	pub fn is_empty(&self) -> bool {
+		self.gamepads.is_empty()
+	}
+
+	/// Deadzone of the axes (between 0 and 1) of every gamepad
+	pub fn set_deadzone(&mut self, deadzone: f64) {
+		self.deadzone = deadzone.clamp(0.0, 0.99);
+		self.gamepads.iter_mut().for_each(|gamepad| gamepad.deadzone = self.deadzone);
+	}
+
+	/// State of the button on any gamepad (the most advanced one)
+	pub fn any_button(&self, button: Button) -> KeyState {
+		KeyState::most_advanced(self.gamepads.iter().map(|gamepad| *gamepad.get_button(button)))
+	}
+
+	/// Position of the axis on any gamepad (the furthest from the center)
+	pub fn any_axis(&self, axis: Axis) -> f64 {
+		furthest_axis(self.gamepads.iter().map(|gamepad| gamepad.get_axis(axis)))
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn assert_close(a: f64, b: f64) {
+		assert!((a - b).abs() < 1e-9, "{} != {}", a, b);
+	}
+
+	#[test]
+	fn axis_range() {
+		assert_eq!(normalize_axis(i16::MIN), -1.0);
+		assert_eq!(normalize_axis(0), 0.0);
+		assert_eq!(normalize_axis(i16::MAX), 1.0);
+	}
+
+	#[test]
+	fn deadzone_is_removed_and_rescaled() {
+		assert_eq!(apply_deadzone(0.1, 0.15), 0.0);
+		assert_eq!(apply_deadzone(-0.15, 0.15), 0.0);
+		assert_close(apply_deadzone(0.575, 0.15), 0.5);
+		assert_close(apply_deadzone(-0.575, 0.15), -0.5);
+		assert_close(apply_deadzone(1.0, 0.15), 1.0);
+		assert_close(apply_deadzone(-1.0, 0.15), -1.0);
+		assert_eq!(apply_deadzone(0.3, 0.0), 0.3);
+	}
+
+	#[test]
+	fn radial_deadzone_keeps_the_direction() {
+		assert_eq!(apply_radial_deadzone(Vector2::new(0.1, 0.1), 0.15), Vector2::zeros());
+		assert_eq!(apply_radial_deadzone(Vector2::zeros(), 0.0), Vector2::zeros());
+
+		let diagonal = apply_radial_deadzone(Vector2::new(0.5, 0.5), 0.15);
+		assert_close(diagonal.x, diagonal.y);
+		assert_close(diagonal.norm(), (0.5_f64.sqrt() - 0.15) / 0.85);
+
+		// A corner of the square range doesn't go beyond 1
+		assert_close(apply_radial_deadzone(Vector2::new(1.0, -1.0), 0.15).norm(), 1.0);
+	}
+
+	#[test]
+	fn any_axis_and_button() {
+		assert_eq!(furthest_axis([0.2, -0.7, 0.5].into_iter()), -0.7);
+		assert_eq!(furthest_axis(std::iter::empty()), 0.0);
+
+		let states = [KeyState::Up, KeyState::Pressed, KeyState::Released];
+		assert_eq!(KeyState::most_advanced(states.into_iter()), KeyState::Pressed);
+		assert_eq!(KeyState::most_advanced([KeyState::Pressed, KeyState::Down].into_iter()), KeyState::Down);
+		assert_eq!(KeyState::most_advanced(std::iter::empty()), KeyState::Up);
+	}
+}
@@ -0,0 +1,208 @@
+use crate::input::shortcut::split_unescaped;
+use crate::input::{KeysState, Shortcut};
+use std::collections::HashMap;
+use std::path::Path;
+
+/// Registry of the keyboard shortcuts of the actions of an app (like "undo" or "reset").
+///
+/// Each action is registered with default shortcuts, that users can rebind.
+/// The bindings are saved as text, with one line per action: `redo = Ctrl+Y | Ctrl+Shift+Z`
+/// (the separators in the key names are escaped with a backslash: `Ctrl+Keypad\ \+`)
+pub struct KeyBindings {
+	bindings: HashMap<String, Vec<Shortcut>>,
+	defaults: HashMap<String, Vec<Shortcut>>,
+}
+
+impl Default for KeyBindings {
+	/// The text editing actions used by the widgets
+	fn default() -> Self {
+		let mut key_bindings = Self::new();
+		key_bindings.register("copy", vec![Shortcut::COPY()]);
+		key_bindings.register("paste", vec![Shortcut::PASTE()]);
+		key_bindings.register("cut", vec![Shortcut::CUT()]);
+		key_bindings.register("select all", vec![Shortcut::SELECT_ALL()]);
+		key_bindings.register("undo", vec![Shortcut::UNDO()]);
+		key_bindings.register("redo", vec![Shortcut::REDO(), "Ctrl+Shift+Z".parse().unwrap()]);
+		key_bindings
+	}
+}
+
+impl KeyBindings {
+	const SEPARATOR: &'static str = " | ";
+
+	/// Bindings without any action
+	pub fn new() -> Self {
+		Self { bindings: HashMap::new(), defaults: HashMap::new() }
+	}
+
+	/// Adds an action with its default shortcuts
+	pub fn register(&mut self, action: &str, shortcuts: Vec<Shortcut>) {
+		self.defaults.insert(action.to_string(), shortcuts.clone());
+		self.bindings.insert(action.to_string(), shortcuts);
+	}
+
+	pub fn get(&self, action: &str) -> &[Shortcut] {
+		self.bindings.get(action).map_or(&[], |shortcuts| shortcuts.as_slice())
+	}
+
+	/// Actions (other than 'action') that would be triggered by one of the shortcuts, sorted by name
+	pub fn get_conflicts(&self, action: &str, shortcuts: &[Shortcut]) -> Vec<String> {
+		let mut conflicts = self
+			.bindings
+			.iter()
+			.filter(|(other_action, other_shortcuts)| {
+				*other_action != action
+					&& shortcuts
+						.iter()
+						.any(|shortcut| other_shortcuts.iter().any(|other| shortcut.conflicts_with(other)))
+			})
+			.map(|(other_action, _)| other_action.clone())
+			.collect::<Vec<String>>();
+		conflicts.sort();
+		conflicts
+	}
+
+	/// Every pair of actions sharing a shortcut
+	pub fn all_conflicts(&self) -> Vec<(String, String)> {
+		let mut actions = self.bindings.keys().collect::<Vec<&String>>();
+		actions.sort();
+		let mut conflicts = Vec::new();
+		for action in actions {
+			for other_action in self.get_conflicts(action, self.get(action)) {
+				if *action < other_action {
+					conflicts.push((action.clone(), other_action));
+				}
+			}
+		}
+		conflicts
+	}
+
+	/// Binds new shortcuts to an action.
+	///
+	/// If other actions already use one of them, nothing changes and the conflicting actions are returned
+	pub fn rebind(&mut self, action: &str, shortcuts: Vec<Shortcut>) -> Result<(), Vec<String>> {
+		let conflicts = self.get_conflicts(action, &shortcuts);
+		if !conflicts.is_empty() {
+			return Err(conflicts);
+		}
+		self.bindings.insert(action.to_string(), shortcuts);
+		Ok(())
+	}
+
+	/// Binds back the default shortcuts of an action
+	pub fn reset(&mut self, action: &str) {
+		if let Some(shortcuts) = self.defaults.get(action) {
+			self.bindings.insert(action.to_string(), shortcuts.clone());
+		}
+	}
+
+	pub fn reset_all(&mut self) {
+		self.bindings = self.defaults.clone();
+	}
+
+	pub fn is_pressed(&self, keys_state: &KeysState, action: &str) -> bool {
+		self.get(action).iter().any(|shortcut| keys_state.shortcut_pressed(shortcut))
+	}
+
+	/// The bindings as text, one line per action sorted by name
+	pub fn save(&self) -> String {
+		let mut lines = self
+			.bindings
+			.iter()
+			.map(|(action, shortcuts)| {
+				let shortcuts = shortcuts.iter().map(|shortcut| shortcut.to_string()).collect::<Vec<String>>();
+				format!("{} = {}", action, shortcuts.join(Self::SEPARATOR))
+			})
+			.collect::<Vec<String>>();
+		lines.sort();
+		lines.join("\n")
+	}
+
+	/// Loads bindings saved with `save`, the actions that are not in the text keep their shortcuts
+	pub fn load(&mut self, text: &str) -> Result<(), String> {
+		let mut bindings = Vec::new();
+		for (index, line) in text.lines().enumerate().filter(|(_, line)| !line.trim().is_empty()) {
+			let (action, shortcuts) = line.split_once('=').ok_or(format!("Missing '=' line {}", index + 1))?;
+			let shortcuts = split_unescaped(shortcuts, '|')
+				.into_iter()
+				.filter(|shortcut| !shortcut.trim().is_empty())
+				.map(str::parse)
+				.collect::<Result<Vec<Shortcut>, String>>()
+				.map_err(|error| format!("{} line {}", error, index + 1))?;
+			bindings.push((action.trim().to_string(), shortcuts));
+		}
+		self.bindings.extend(bindings);
+		Ok(())
+	}
+
+	pub fn save_to_file(&self, path: impl AsRef<Path>) -> std::io::Result<()> {
+		std::fs::write(path, self.save())
+	}
+
+	pub fn load_from_file(&mut self, path: impl AsRef<Path>) -> Result<(), String> {
+		let text = std::fs::read_to_string(path).map_err(|error| error.to_string())?;
+		self.load(&text)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use sdl2::keyboard::{Keycode, Mod};
+
+	#[test]
+	fn save_then_load() {
+		let mut key_bindings = KeyBindings::default();
+		key_bindings.register("reset", vec!["Ctrl+R".parse().unwrap()]);
+		key_bindings.rebind("undo", vec!["Ctrl+K Ctrl+U".parse().unwrap(), "Alt+Backspace".parse().unwrap()]).unwrap();
+		let text = key_bindings.save();
+		assert!(text.contains("undo = Ctrl+K Ctrl+U | Alt+Backspace"));
+		assert!(text.contains("redo = Ctrl+Y | Ctrl+Shift+Z"));
+
+		let mut loaded = KeyBindings::default();
+		loaded.load(&text).unwrap();
+		assert_eq!(loaded.save(), text);
+		assert_eq!(loaded.get("reset"), key_bindings.get("reset"));
+	}
+
+	#[test]
+	fn save_then_load_keys_with_separators() {
+		let mut key_bindings = KeyBindings::default();
+		let shortcuts = ["Keypad 1", "Keypad +", "Keypad |"]
+			.map(|name| Shortcut::new(Mod::LCTRLMOD | Mod::RCTRLMOD, Keycode::from_name(name).unwrap()));
+		key_bindings.register("zoom", shortcuts.to_vec());
+		let text = key_bindings.save();
+		assert!(text.contains(r"zoom = Ctrl+Keypad\ 1 | Ctrl+Keypad\ \+ | Ctrl+Keypad\ \|"));
+
+		let mut loaded = KeyBindings::default();
+		loaded.load(&text).unwrap();
+		assert_eq!(loaded.get("zoom"), &shortcuts);
+	}
+
+	#[test]
+	fn load_keeps_the_missing_actions() {
+		let mut key_bindings = KeyBindings::default();
+		key_bindings.load("\ncopy = Ctrl+Insert\n\n").unwrap();
+		assert_eq!(key_bindings.get("copy"), &["Ctrl+Insert".parse::<Shortcut>().unwrap()]);
+		assert_eq!(key_bindings.get("paste"), &[Shortcut::PASTE()]);
+		key_bindings.reset("copy");
+		assert_eq!(key_bindings.get("copy"), &[Shortcut::COPY()]);
+	}
+
+	#[test]
+	fn load_errors() {
+		let mut key_bindings = KeyBindings::default();
+		assert_eq!(key_bindings.load("copy Ctrl+C"), Err("Missing '=' line 1".to_string()));
+		assert!(key_bindings.load("copy = Ctrl+C\npaste = Ctrl+Nope").unwrap_err().ends_with("line 2"));
+		// Nothing is loaded from an invalid text
+		assert_eq!(key_bindings.get("copy"), &[Shortcut::COPY()]);
+	}
+
+	#[test]
+	fn rebind_refuses_conflicts() {
+		let mut key_bindings = KeyBindings::default();
+		assert_eq!(key_bindings.rebind("copy", vec![Shortcut::PASTE()]), Err(vec!["paste".to_string()]));
+		assert_eq!(key_bindings.get("copy"), &[Shortcut::COPY()]);
+		assert!(key_bindings.all_conflicts().is_empty());
+	}
+}
@@ -1,4 +1,7 @@
-use sdl2::keyboard::Keycode;
+use crate::input::shortcut::{is_modifier_key, main_modifiers, KeyChord};
+use crate::input::Shortcut;
+use sdl2::keyboard::{Keycode, Mod, Scancode};
+use std::collections::{HashMap, VecDeque};
 
 #[derive(PartialEq, Eq, Debug, Clone, Copy)]
 pub enum KeyState {
@@ -8,82 +11,16 @@ pub enum KeyState {
 	Released,
 }
 
-// TODO suggestion key state avec double press
-#[derive(PartialEq, Eq, Debug, Clone)]
-pub enum ChadKeyState {
-	Up { released_time: std::time::Instant },
-	Down,
-	Released,
-	Pressed,
-	DoublePressed,
-}
-
-impl ChadKeyState {
-	const TIME_TO_CONSECUTIVE_MS: u128 = 100;
-
-	pub fn update(&mut self) {
-		match self {
-			Self::Pressed | Self::DoublePressed => {
-				*self = Self::Down;
-			}
-			Self::Released => {
-				*self = Self::Up { released_time: std::time::Instant::now() };
-			}
-			_ => {}
-		};
-	}
-
-	pub fn press(&mut self) {
-		*self = match self {
-			Self::Up { released_time } => {
-				if released_time.elapsed().as_millis() < Self::TIME_TO_CONSECUTIVE_MS {
-					Self::DoublePressed
-				} else {
-					Self::Pressed
-				}
-			}
-			// TODO spam click un switch fait crash l'app
-			_ => panic!("Cannot press a key that is not up or released ({:?})", self),
-		};
-	}
-
-	pub fn release(&mut self) {
-		*self = match self {
-			Self::Down => Self::Released,
-			_ => panic!("Cannot release a key that is not pressed or down (key is {:?})", self),
-		};
-	}
-
-	pub fn is_up(&self) -> bool {
-		match self {
-			Self::Up { .. } => true,
-			_ => false,
-		}
-	}
-	pub fn is_pressed(&self) -> bool {
-		*self == Self::Pressed
-	}
-	pub fn is_down(&self) -> bool {
-		match self {
-			Self::Down { .. } => true,
-			_ => false,
-		}
-	}
-	pub fn is_released(&self) -> bool {
-		match self {
-			Self::Released { .. } => true,
-			_ => false,
-		}
-	}
-	pub fn is_double_pressed(&self) -> bool {
-		*self == Self::DoublePressed
-	}
-}
-
 impl KeyState {
 	pub fn new() -> Self {
 		Self::Up
 	}
+
+	/// State of several inputs seen as one: down if one is down, even if another one was just pressed
+	pub fn most_advanced(states: impl Iterator<Item = KeyState>) -> Self {
+		let states = states.collect::<Vec<KeyState>>();
+		[Self::Down, Self::Pressed, Self::Released].into_iter().find(|state| states.contains(state)).unwrap_or(Self::Up)
+	}
 	pub fn update(&mut self) {
 		match self {
 			Self::Pressed => {
@@ -117,343 +54,263 @@ impl KeyState {
 	}
 }
 
-#[derive(Debug)]
-pub struct KeysState {
-	pub a: KeyState,
-	pub b: KeyState,
-	pub c: KeyState,
-	pub d: KeyState,
-	pub e: KeyState,
-	pub f: KeyState,
-	pub g: KeyState,
-	pub h: KeyState,
-	pub i: KeyState,
-	pub j: KeyState,
-	pub k: KeyState,
-	pub l: KeyState,
-	pub m: KeyState,
-	pub n: KeyState,
-	pub o: KeyState,
-	pub p: KeyState,
-	pub q: KeyState,
-	pub r: KeyState,
-	pub s: KeyState,
-	pub t: KeyState,
-	pub u: KeyState,
-	pub v: KeyState,
-	pub w: KeyState,
-	pub x: KeyState,
-	pub y: KeyState,
-	pub z: KeyState,
-	pub up: KeyState,
-	pub down: KeyState,
-	pub left: KeyState,
-	pub right: KeyState,
-	pub _0: KeyState,
-	pub _1: KeyState,
-	pub _2: KeyState,
-	pub _3: KeyState,
-	pub _4: KeyState,
-	pub _5: KeyState,
-	pub _6: KeyState,
-	pub _7: KeyState,
-	pub _8: KeyState,
-	pub _9: KeyState,
-	pub space: KeyState,
-	pub enter: KeyState,
-	pub mouse_left: KeyState,
-	pub mouse_right: KeyState,
-	pub mouse_middle: KeyState,
-	pub escape: KeyState,
-	pub backspace: KeyState,
-	pub lctrl: KeyState,
-	pub rctrl: KeyState,
-	pub tab: KeyState,
-	pub lshift: KeyState,
-	pub rshift: KeyState,
-	pub lalt: KeyState,
-	pub ralt: KeyState,
-	pub lgui: KeyState,
-	pub rgui: KeyState,
+/// Defines `KeysState` with a public field for each given key, the other keys are in a map
+macro_rules! keys_state {
+	($($name:ident => $keycode:ident),* $(,)?) => {
+		/// State of every key of the keyboard, by keycode (the symbol of the key in the keyboard layout)
+		/// and by scancode (the physical position of the key).
+		///
+		/// The usual keys also have named fields, like `keys_state.enter`
+		#[derive(Debug)]
+		pub struct KeysState {
+			$(pub $name: KeyState,)*
+			// The mouse buttons, mirroring `Input::mouse`
+			pub mouse_left: KeyState,
+			pub mouse_right: KeyState,
+			pub mouse_middle: KeyState,
+			/// Keys without a named field that are not up (the missing keys are up)
+			keys: HashMap<Keycode, KeyState>,
+			scancodes: [KeyState; Scancode::Num as usize],
+			/// Modifiers held during the last key event
+			modifiers: Mod,
+			/// Last presses of non-modifier keys, with the modifiers held and the timestamps of their events,
+			/// to detect the sequences of chords
+			history: VecDeque<(Keycode, Mod, u32)>,
+		}
+
+		impl KeysState {
+			pub fn new() -> Self {
+				KeysState {
+					$($name: KeyState::Up,)*
+					mouse_left: KeyState::Up,
+					mouse_right: KeyState::Up,
+					mouse_middle: KeyState::Up,
+					keys: HashMap::new(),
+					scancodes: [KeyState::Up; Scancode::Num as usize],
+					modifiers: Mod::NOMOD,
+					history: VecDeque::new(),
+				}
+			}
+
+			fn named_key(&self, keycode: Keycode) -> Option<&KeyState> {
+				match keycode {
+					$(Keycode::$keycode => Some(&self.$name),)*
+					_ => None,
+				}
+			}
+
+			fn named_key_mut(&mut self, keycode: Keycode) -> Option<&mut KeyState> {
+				match keycode {
+					$(Keycode::$keycode => Some(&mut self.$name),)*
+					_ => None,
+				}
+			}
+
+			fn named_keys_mut(&mut self) -> impl Iterator<Item = &mut KeyState> {
+				[$(&mut self.$name,)* &mut self.mouse_left, &mut self.mouse_right, &mut self.mouse_middle].into_iter()
+			}
+		}
+	};
 }
 
+keys_state!(
+	a => A, b => B, c => C, d => D, e => E, f => F, g => G, h => H, i => I, j => J, k => K, l => L, m => M,
+	n => N, o => O, p => P, q => Q, r => R, s => S, t => T, u => U, v => V, w => W, x => X, y => Y, z => Z,
+	_0 => Num0, _1 => Num1, _2 => Num2, _3 => Num3, _4 => Num4, _5 => Num5, _6 => Num6, _7 => Num7, _8 => Num8,
+	_9 => Num9,
+	up => Up, down => Down, left => Left, right => Right,
+	space => Space, enter => Return, escape => Escape, backspace => Backspace, tab => Tab,
+	delete => Delete, home => Home, end => End,
+	lctrl => LCtrl, rctrl => RCtrl, lshift => LShift, rshift => RShift, lalt => LAlt, ralt => RAlt,
+	lgui => LGui, rgui => RGui,
+);
+
 impl KeysState {
-	pub fn new() -> Self {
-		KeysState {
-			a: KeyState::new(),
-			b: KeyState::new(),
-			c: KeyState::new(),
-			d: KeyState::new(),
-			e: KeyState::new(),
-			f: KeyState::new(),
-			g: KeyState::new(),
-			h: KeyState::new(),
-			i: KeyState::new(),
-			j: KeyState::new(),
-			k: KeyState::new(),
-			l: KeyState::new(),
-			m: KeyState::new(),
-			n: KeyState::new(),
-			o: KeyState::new(),
-			p: KeyState::new(),
-			q: KeyState::new(),
-			r: KeyState::new(),
-			s: KeyState::new(),
-			t: KeyState::new(),
-			u: KeyState::new(),
-			v: KeyState::new(),
-			w: KeyState::new(),
-			x: KeyState::new(),
-			y: KeyState::new(),
-			z: KeyState::new(),
-			up: KeyState::new(),
-			down: KeyState::new(),
-			left: KeyState::new(),
-			right: KeyState::new(),
-			_0: KeyState::new(),
-			_1: KeyState::new(),
-			_2: KeyState::new(),
-			_3: KeyState::new(),
-			_4: KeyState::new(),
-			_5: KeyState::new(),
-			_6: KeyState::new(),
-			_7: KeyState::new(),
-			_8: KeyState::new(),
-			_9: KeyState::new(),
-			space: KeyState::new(),
-			enter: KeyState::new(),
-			mouse_left: KeyState::new(),
-			mouse_right: KeyState::new(),
-			mouse_middle: KeyState::new(),
-			escape: KeyState::new(),
-			backspace: KeyState::new(),
-			lctrl: KeyState::new(),
-			rctrl: KeyState::new(),
-			tab: KeyState::new(),
-			lshift: KeyState::new(),
-			rshift: KeyState::new(),
-			lalt: KeyState::new(),
-			ralt: KeyState::new(),
-			lgui: KeyState::new(),
-			rgui: KeyState::new(),
-		}
+	/// Number of presses remembered for the sequences of chords
+	const HISTORY_SIZE: usize = 8;
+	/// Maximum time between two presses of a sequence (in ms)
+	const SEQUENCE_TIMEOUT: u32 = 1000;
+
+	/// Should be called every frame, before the events are handled
+	pub fn update(&mut self) {
+		self.named_keys_mut().for_each(|key_state| key_state.update());
+		self.keys.values_mut().for_each(|key_state| key_state.update());
+		self.keys.retain(|_, key_state| !key_state.is_up());
+		self.scancodes.iter_mut().for_each(|key_state| key_state.update());
 	}
 
 	pub fn get_key(&self, keycode: Keycode) -> &KeyState {
-		match keycode {
-			Keycode::Backspace => &self.backspace,
-			Keycode::A => &self.a,
-			Keycode::B => &self.b,
-			Keycode::C => &self.c,
-			Keycode::D => &self.d,
-			Keycode::E => &self.e,
-			Keycode::F => &self.f,
-			Keycode::G => &self.g,
-			Keycode::H => &self.h,
-			Keycode::I => &self.i,
-			Keycode::J => &self.j,
-			Keycode::K => &self.k,
-			Keycode::L => &self.l,
-			Keycode::M => &self.m,
-			Keycode::N => &self.n,
-			Keycode::O => &self.o,
-			Keycode::P => &self.p,
-			Keycode::Q => &self.q,
-			Keycode::R => &self.r,
-			Keycode::S => &self.s,
-			Keycode::T => &self.t,
-			Keycode::U => &self.u,
-			Keycode::V => &self.v,
-			Keycode::W => &self.w,
-			Keycode::X => &self.x,
-			Keycode::Y => &self.y,
-			Keycode::Z => &self.z,
-			Keycode::Up => &self.up,
-			Keycode::Down => &self.down,
-			Keycode::Left => &self.left,
-			Keycode::Right => &self.right,
-			Keycode::Num0 => &self._0,
-			Keycode::Num1 => &self._1,
-			Keycode::Num2 => &self._2,
-			Keycode::Num3 => &self._3,
-			Keycode::Num4 => &self._4,
-			Keycode::Num5 => &self._5,
-			Keycode::Num6 => &self._6,
-			Keycode::Num7 => &self._7,
-			Keycode::Num8 => &self._8,
-			Keycode::Num9 => &self._9,
-			Keycode::Space => &self.space,
-			Keycode::Return => &self.enter,
-			Keycode::LShift => &self.lshift,
-			Keycode::RShift => &self.rshift,
-			Keycode::LCtrl => &self.lctrl,
-			Keycode::RCtrl => &self.rctrl,
-			Keycode::LAlt => &self.lalt,
-			Keycode::RAlt => &self.ralt,
-			Keycode::Escape => &self.escape,
-			Keycode::Tab => &self.tab,
-			Keycode::LGui => &self.lgui,
-			Keycode::RGui => &self.rgui,
-			_ => todo!("Keycode {:?} not implemented", keycode),
-		}
+		self.named_key(keycode).or(self.keys.get(&keycode)).unwrap_or(&KeyState::Up)
 	}
 
 	fn get_key_mut(&mut self, keycode: Keycode) -> &mut KeyState {
-		match keycode {
-			Keycode::Backspace => &mut self.backspace,
-			Keycode::A => &mut self.a,
-			Keycode::B => &mut self.b,
-			Keycode::C => &mut self.c,
-			Keycode::D => &mut self.d,
-			Keycode::E => &mut self.e,
-			Keycode::F => &mut self.f,
-			Keycode::G => &mut self.g,
-			Keycode::H => &mut self.h,
-			Keycode::I => &mut self.i,
-			Keycode::J => &mut self.j,
-			Keycode::K => &mut self.k,
-			Keycode::L => &mut self.l,
-			Keycode::M => &mut self.m,
-			Keycode::N => &mut self.n,
-			Keycode::O => &mut self.o,
-			Keycode::P => &mut self.p,
-			Keycode::Q => &mut self.q,
-			Keycode::R => &mut self.r,
-			Keycode::S => &mut self.s,
-			Keycode::T => &mut self.t,
-			Keycode::U => &mut self.u,
-			Keycode::V => &mut self.v,
-			Keycode::W => &mut self.w,
-			Keycode::X => &mut self.x,
-			Keycode::Y => &mut self.y,
-			Keycode::Z => &mut self.z,
-			Keycode::Escape => &mut self.escape,
-			Keycode::Up => &mut self.up,
-			Keycode::Down => &mut self.down,
-			Keycode::Left => &mut self.left,
-			Keycode::Right => &mut self.right,
-			Keycode::Num0 => &mut self._0,
-			Keycode::Num1 => &mut self._1,
-			Keycode::Num2 => &mut self._2,
-			Keycode::Num3 => &mut self._3,
-			Keycode::Num4 => &mut self._4,
-			Keycode::Num5 => &mut self._5,
-			Keycode::Num6 => &mut self._6,
-			Keycode::Num7 => &mut self._7,
-			Keycode::Num8 => &mut self._8,
-			Keycode::Num9 => &mut self._9,
-			Keycode::Space => &mut self.space,
-			Keycode::Return => &mut self.enter,
-			Keycode::LCtrl => &mut self.lctrl,
-			Keycode::RCtrl => &mut self.rctrl,
-			Keycode::LShift => &mut self.lshift,
-			Keycode::RShift => &mut self.rshift,
-			Keycode::LAlt => &mut self.lalt,
-			Keycode::RAlt => &mut self.ralt,
-			Keycode::Tab => &mut self.tab,
-			Keycode::LGui => &mut self.lgui,
-			Keycode::RGui => &mut self.rgui,
-			_ => todo!("Keycode {:?} not implemented", keycode),
+		match self.named_key(keycode) {
+			Some(_) => self.named_key_mut(keycode).unwrap(),
+			None => self.keys.entry(keycode).or_insert(KeyState::Up),
 		}
 	}
 
-	pub fn press_key(&mut self, keycode: Keycode) {
+	pub fn get_scancode(&self, scancode: Scancode) -> &KeyState {
+		&self.scancodes[scancode as usize]
+	}
+
+	pub fn get_modifiers(&self) -> Mod {
+		self.modifiers
+	}
+
+	pub fn set_modifiers(&mut self, modifiers: Mod) {
+		self.modifiers = modifiers;
+	}
+
+	/// 'repeat' is true when the key is held down and the system repeats the press,
+	/// 'timestamp' is the one of the SDL event (in ms), so that replayed sequences behave the same
+	pub fn press_key(&mut self, keycode: Keycode, repeat: bool, timestamp: u32) {
 		self.get_key_mut(keycode).press();
+		if !repeat && !is_modifier_key(keycode) {
+			if self.history.len() == Self::HISTORY_SIZE {
+				self.history.pop_front();
+			}
+			self.history.push_back((keycode, self.modifiers, timestamp));
+		}
 	}
 
 	pub fn release_key(&mut self, keycode: Keycode) {
 		self.get_key_mut(keycode).release();
 	}
 
-	pub fn as_mut_array(&mut self) -> [&mut KeyState; 56] {
-		[
-			&mut self.a,
-			&mut self.b,
-			&mut self.c,
-			&mut self.d,
-			&mut self.e,
-			&mut self.f,
-			&mut self.g,
-			&mut self.h,
-			&mut self.i,
-			&mut self.j,
-			&mut self.k,
-			&mut self.l,
-			&mut self.m,
-			&mut self.n,
-			&mut self.o,
-			&mut self.p,
-			&mut self.q,
-			&mut self.r,
-			&mut self.s,
-			&mut self.t,
-			&mut self.u,
-			&mut self.v,
-			&mut self.w,
-			&mut self.x,
-			&mut self.y,
-			&mut self.z,
-			&mut self.up,
-			&mut self.down,
-			&mut self.left,
-			&mut self.right,
-			&mut self.space,
-			&mut self.enter,
-			&mut self.mouse_left,
-			&mut self.mouse_right,
-			&mut self.mouse_middle,
-			&mut self.escape,
-			&mut self.backspace,
-			&mut self._0,
-			&mut self._1,
-			&mut self._2,
-			&mut self._3,
-			&mut self._4,
-			&mut self._5,
-			&mut self._6,
-			&mut self._7,
-			&mut self._8,
-			&mut self._9,
-			&mut self.ralt,
-			&mut self.lalt,
-			&mut self.rctrl,
-			&mut self.lctrl,
-			&mut self.rshift,
-			&mut self.lshift,
-			&mut self.tab,
-			&mut self.lgui,
-			&mut self.rgui,
-		]
+	pub fn press_scancode(&mut self, scancode: Scancode) {
+		self.scancodes[scancode as usize].press();
+	}
+
+	pub fn release_scancode(&mut self, scancode: Scancode) {
+		self.scancodes[scancode as usize].release();
 	}
 
+	/// Whether the keys of the chord are held with exactly its modifiers, one of them being pressed this frame
+	pub fn chord_pressed(&self, chord: &KeyChord) -> bool {
+		main_modifiers(self.modifiers) == main_modifiers(chord.get_modifiers())
+			&& chord.get_keys().iter().all(|key| !self.get_key(*key).is_up() && !self.get_key(*key).is_released())
+			&& chord.get_keys().iter().any(|key| self.get_key(*key).is_pressed())
+	}
+
+	/// Whether the last chord of the shortcut is pressed this frame, right after the previous chords.
+	///
+	/// The chords of a sequence are matched by the last presses of the history (one per key of each chord)
 	pub fn shortcut_pressed(&self, shortcut: &Shortcut) -> bool {
-		self.get_key(shortcut.key).is_pressed()
-			&& shortcut.ctrl_keys.iter().all(|keys| keys.iter().any(|key| self.get_key(*key).is_down()))
+		let Some((last_chord, previous_chords)) = shortcut.get_chords().split_last() else {
+			return false;
+		};
+		if !self.chord_pressed(last_chord) {
+			return false;
+		}
+		if previous_chords.is_empty() {
+			return true;
+		}
+
+		// The last presses of the history are the ones of the last chord
+		let mut presses = self.history.iter().rev();
+		let Some(mut next_time) = presses.by_ref().take(last_chord.get_keys().len()).last().map(|(_, _, time)| *time)
+		else {
+			return false;
+		};
+		for chord in previous_chords.iter().rev() {
+			for _ in chord.get_keys() {
+				let Some((keycode, modifiers, time)) = presses.next() else {
+					return false;
+				};
+				if !chord.get_keys().contains(keycode)
+					|| main_modifiers(chord.get_modifiers()) != main_modifiers(*modifiers)
+					|| next_time.wrapping_sub(*time) > Self::SEQUENCE_TIMEOUT
+				{
+					return false;
+				}
+				next_time = *time;
+			}
+		}
+		true
 	}
 }
 
-pub struct Shortcut {
-	ctrl_keys: Vec<Vec<Keycode>>,
-	key: Keycode,
-}
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::input::KeyChord;
+
+	fn chord(keys: &[Keycode]) -> KeyChord {
+		KeyChord::new(Mod::NOMOD, keys.to_vec())
+	}
+
+	/// Starts a new frame, then presses the keys at 'timestamp'
+	fn press(keys_state: &mut KeysState, keys: &[Keycode], timestamp: u32) {
+		keys_state.update();
+		keys.iter().for_each(|key| keys_state.press_key(*key, false, timestamp));
+	}
 
-impl Shortcut {
-	pub fn new(ctrl_keys: Vec<Vec<Keycode>>, key: Keycode) -> Self {
-		Self { ctrl_keys, key }
+	fn release(keys_state: &mut KeysState, keys: &[Keycode]) {
+		keys_state.update();
+		keys.iter().for_each(|key| keys_state.release_key(*key));
 	}
 
-	#[allow(non_snake_case)]
-	pub fn COPY() -> Self {
-		Self::new(vec![vec![Keycode::LCtrl, Keycode::RCtrl]], Keycode::C)
+	#[test]
+	fn named_fields_follow_the_keys() {
+		let mut keys_state = KeysState::new();
+		press(&mut keys_state, &[Keycode::Return, Keycode::F1], 0);
+		assert!(keys_state.enter.is_pressed());
+		assert!(keys_state.get_key(Keycode::Return).is_pressed());
+		assert!(keys_state.get_key(Keycode::F1).is_pressed());
+		keys_state.update();
+		assert!(keys_state.enter.is_down());
+		release(&mut keys_state, &[Keycode::Return]);
+		assert!(keys_state.enter.is_released());
 	}
 
-	#[allow(non_snake_case)]
-	pub fn PASTE() -> Self {
-		Self::new(vec![vec![Keycode::LCtrl, Keycode::RCtrl]], Keycode::V)
+	#[test]
+	fn sequence_ending_with_a_chord_of_several_keys() {
+		let shortcut = Shortcut::from_chords(vec![chord(&[Keycode::K]), chord(&[Keycode::A, Keycode::S])]);
+		let mut keys_state = KeysState::new();
+		press(&mut keys_state, &[Keycode::K], 0);
+		release(&mut keys_state, &[Keycode::K]);
+		press(&mut keys_state, &[Keycode::A], 200);
+		assert!(!keys_state.shortcut_pressed(&shortcut));
+		press(&mut keys_state, &[Keycode::S], 250);
+		assert!(keys_state.shortcut_pressed(&shortcut));
 	}
 
-	#[allow(non_snake_case)]
-	pub fn CUT() -> Self {
-		Self::new(vec![vec![Keycode::LCtrl, Keycode::RCtrl]], Keycode::X)
+	#[test]
+	fn sequence_starting_with_a_chord_of_several_keys() {
+		let shortcut = Shortcut::from_chords(vec![chord(&[Keycode::A, Keycode::S]), chord(&[Keycode::K])]);
+		let mut keys_state = KeysState::new();
+		press(&mut keys_state, &[Keycode::A, Keycode::S], 0);
+		release(&mut keys_state, &[Keycode::A, Keycode::S]);
+		press(&mut keys_state, &[Keycode::K], 300);
+		assert!(keys_state.shortcut_pressed(&shortcut));
+	}
+
+	#[test]
+	fn sequence_times_out() {
+		let shortcut = Shortcut::from_chords(vec![chord(&[Keycode::K]), chord(&[Keycode::C])]);
+		let mut keys_state = KeysState::new();
+		press(&mut keys_state, &[Keycode::K], 0);
+		release(&mut keys_state, &[Keycode::K]);
+		press(&mut keys_state, &[Keycode::C], KeysState::SEQUENCE_TIMEOUT + 1);
+		assert!(!keys_state.shortcut_pressed(&shortcut));
+	}
+
+	#[test]
+	fn sequence_needs_its_modifiers() {
+		let ctrl_k = KeyChord::new(Mod::LCTRLMOD, vec![Keycode::K]);
+		let shortcut = Shortcut::from_chords(vec![ctrl_k, chord(&[Keycode::C])]);
+		let mut keys_state = KeysState::new();
+		press(&mut keys_state, &[Keycode::K], 0);
+		release(&mut keys_state, &[Keycode::K]);
+		press(&mut keys_state, &[Keycode::C], 100);
+		assert!(!keys_state.shortcut_pressed(&shortcut));
+
+		keys_state.set_modifiers(Mod::RCTRLMOD);
+		press(&mut keys_state, &[Keycode::K], 200);
+		keys_state.set_modifiers(Mod::NOMOD);
+		release(&mut keys_state, &[Keycode::K]);
+		press(&mut keys_state, &[Keycode::C], 300);
+		assert!(keys_state.shortcut_pressed(&shortcut));
 	}
 }
@@ -1,72 +1,202 @@
+mod action_map;
+mod cursor;
+mod gamepad;
+mod key_bindings;
 mod key_state;
 mod mouse;
+mod shortcut;
+mod touch;
 
-pub use key_state::{KeyState, KeysState, Shortcut};
+pub use action_map::{ActionBinding, ActionMap, AxisBinding};
+pub use cursor::{Cursors, MouseCursor};
+pub use gamepad::{Gamepad, Gamepads};
+pub use key_bindings::KeyBindings;
+pub use key_state::{KeyState, KeysState};
+pub use mouse::MouseButtonState;
 use sdl2::clipboard::ClipboardUtil;
+use sdl2::event::Event;
+use sdl2::keyboard::TextInputUtil;
+use sdl2::rect::Rect;
+use sdl2::TimerSubsystem;
+pub use shortcut::{KeyChord, Shortcut};
+use std::cell::{Ref, RefCell, RefMut};
+use std::path::PathBuf;
+pub use touch::{Gesture, Touch, TouchPoint};
+
+/// Text being composed with an input method (IME), before it is committed in `typed_text`
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Composition {
+	pub text: String,
+	/// Position of the cursor in the text (in chars)
+	pub cursor: usize,
+	/// Length of the part of the text selected by the input method (in chars)
+	pub selection_length: usize,
+}
 
 pub struct Input {
 	event_pump: sdl2::EventPump,
 	pub window_closed: bool,
 	pub keys_state: KeysState,
 	pub mouse: mouse::Mouse,
-	pub last_char: Option<char>,
+	pub gamepads: Gamepads,
+	pub touch: Touch,
+	/// Time of the frame (in ms since SDL was initialized), on the clock of the event timestamps.
+	///
+	/// The double clicks and the key sequences are timed with the event timestamps only, so that replayed events
+	/// behave the same. But `get_events` sets the time to the wall clock at each frame, so that a finger held still
+	/// (without events) becomes a long press: the long presses depend on the frame times
+	pub time: u32,
+	timer: TimerSubsystem,
+	/// Text typed since the last frame, it can hold several chars (or none)
+	pub typed_text: String,
+	/// Text being composed with an input method, if any
+	pub composition: Option<Composition>,
+	/// Files dropped on the window from the OS since the last frame
+	pub dropped_files: Vec<PathBuf>,
+	/// Texts dropped on the window from the OS since the last frame
+	pub dropped_texts: Vec<String>,
 	pub clipboard: ClipboardUtil,
+	text_input: TextInputUtil,
+	/// Shared by the app and the widgets, which only get the input by reference
+	key_bindings: RefCell<KeyBindings>,
+	/// Shared like the key bindings
+	action_map: RefCell<ActionMap>,
 }
 
 impl Input {
 	/// can crash
-	pub fn new(sdl_context: sdl2::Sdl, clipboard: ClipboardUtil) -> Self {
+	pub fn new(sdl_context: sdl2::Sdl, clipboard: ClipboardUtil, text_input: TextInputUtil) -> Self {
 		Self {
+			gamepads: Gamepads::new(sdl_context.game_controller().ok()),
 			event_pump: sdl_context.event_pump().unwrap(),
 			window_closed: false,
 			keys_state: KeysState::new(),
 			mouse: mouse::Mouse::new(),
-			last_char: None,
+			touch: Touch::new(),
+			time: 0,
+			timer: sdl_context.timer().unwrap(),
+			typed_text: String::new(),
+			composition: None,
+			dropped_files: Vec::new(),
+			dropped_texts: Vec::new(),
 			clipboard,
+			text_input,
+			key_bindings: RefCell::new(KeyBindings::default()),
+			action_map: RefCell::new(ActionMap::default()),
 		}
 	}
 
 	/// should be called every frame
 	pub fn get_events(&mut self) {
-		self.last_char = None;
+		self.typed_text.clear();
+		self.dropped_files.clear();
+		self.dropped_texts.clear();
 
-		for key_state in self.keys_state.as_mut_array() {
-			key_state.update()
-		}
+		self.keys_state.update();
 
 		self.mouse.update();
 
-		for event in self.event_pump.poll_iter() {
-			use sdl2::event::Event;
-			self.mouse.get_event(event.clone());
-			match event {
-				Event::TextEditing { text, .. } => {
-					println!("TextEditing {:?}", text);
+		self.gamepads.update();
+
+		self.touch.update();
+
+		// The wall clock, and not the last timestamp, so that the long presses don't wait for an event
+		self.time = self.timer.ticks();
+		let events = self.event_pump.poll_iter().collect::<Vec<Event>>();
+		for event in events {
+			self.handle_event(event);
+		}
+
+		self.touch.recognize_gestures(self.time);
+	}
+
+	/// Updates the input from an event, `get_events` calls it for every SDL event of the frame.
+	///
+	/// Synthetic events can be sent to it to test the input without devices
+	pub fn handle_event(&mut self, event: Event) {
+		self.time = self.time.max(event.get_timestamp());
+		self.mouse.get_event(event.clone());
+		self.keys_state.mouse_left = self.mouse.left_button.get_state();
+		self.keys_state.mouse_right = self.mouse.right_button.get_state();
+		self.keys_state.mouse_middle = self.mouse.middle_button.get_state();
+		self.gamepads.get_event(&event);
+		self.touch.get_event(&event);
+		match event {
+			Event::TextEditing { text, start, length, .. } => {
+				self.composition = if text.is_empty() {
+					None
+				} else {
+					Some(Composition { text, cursor: start.max(0) as usize, selection_length: length.max(0) as usize })
+				};
+			}
+			Event::TextInput { text, .. } => {
+				self.typed_text.push_str(&text);
+				self.composition = None;
+			}
+			Event::Quit { .. } => self.window_closed = true,
+			Event::DropFile { filename, .. } => self.dropped_files.push(PathBuf::from(filename)),
+			Event::DropText { filename, .. } => self.dropped_texts.push(filename),
+			Event::KeyDown { timestamp, keycode, scancode, keymod, repeat, .. } => {
+				self.keys_state.set_modifiers(keymod);
+				if let Some(keycode) = keycode {
+					self.keys_state.press_key(keycode, repeat, timestamp);
 				}
-				Event::TextInput { text, .. } => {
-					if text.chars().count() == 1 {
-						self.last_char = text.chars().next();
-					} else {
-						panic!("TextInput event with more than one char {:?}", text);
-					}
+				if let Some(scancode) = scancode {
+					self.keys_state.press_scancode(scancode);
 				}
-				Event::Quit { .. } => self.window_closed = true,
-				Event::KeyDown { keycode, .. } => {
-					if let Some(keycode) = keycode {
-						self.keys_state.press_key(keycode);
-					}
+			}
+			Event::KeyUp { keycode, scancode, keymod, .. } => {
+				self.keys_state.set_modifiers(keymod);
+				if let Some(keycode) = keycode {
+					self.keys_state.release_key(keycode);
 				}
-				Event::KeyUp { keycode, .. } => {
-					if let Some(keycode) = keycode {
-						self.keys_state.release_key(keycode);
-					}
+				if let Some(scancode) = scancode {
+					self.keys_state.release_scancode(scancode);
 				}
-				_ => {}
 			}
+			_ => {}
 		}
 	}
 
+	/// Tells the input method where the text is typed, so that its candidate window is placed next to it
+	pub fn set_text_input_rect(&self, rect: Rect) {
+		self.text_input.set_rect(rect);
+	}
+
 	pub fn shortcut_pressed(&self, shortcut: &Shortcut) -> bool {
 		self.keys_state.shortcut_pressed(shortcut)
 	}
+
+	pub fn key_bindings(&self) -> Ref<'_, KeyBindings> {
+		self.key_bindings.borrow()
+	}
+
+	/// To register the actions of the app, or to rebind them
+	pub fn key_bindings_mut(&self) -> RefMut<'_, KeyBindings> {
+		self.key_bindings.borrow_mut()
+	}
+
+	/// Whether one of the shortcuts bound to the action is pressed
+	pub fn binding_pressed(&self, action: &str) -> bool {
+		self.key_bindings.borrow().is_pressed(&self.keys_state, action)
+	}
+
+	pub fn action_map(&self) -> Ref<'_, ActionMap> {
+		self.action_map.borrow()
+	}
+
+	/// To bind the actions of the app, or to rebind them
+	pub fn action_map_mut(&self) -> RefMut<'_, ActionMap> {
+		self.action_map.borrow_mut()
+	}
+
+	/// State of a named action, like `input.action("pan").is_pressed()`
+	pub fn action(&self, action: &str) -> KeyState {
+		self.action_map.borrow().get_state(action, self)
+	}
+
+	/// Value of a named axis, like `input.axis("zoom")`
+	pub fn axis(&self, axis: &str) -> f64 {
+		self.action_map.borrow().get_axis(axis, self)
+	}
 }
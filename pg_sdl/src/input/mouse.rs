@@ -1,72 +1,234 @@
-use crate::input::key_state::ChadKeyState;
 use nalgebra::{Point2, Vector2};
-use sdl2::mouse::MouseButton;
-use sdl2::rect::Point;
-use std::time::Instant;
+use sdl2::mouse::{MouseButton, MouseWheelDirection};
 
 use super::KeyState;
 
+/// State of a mouse button, with its consecutive clicks and its drag
+#[derive(Clone, PartialEq, Debug)]
+pub struct MouseButtonState {
+	state: KeyState,
+	/// Number of consecutive clicks of the last press (1 for a simple click, 2 for a double click...)
+	click_count: u32,
+	/// Position where the button was pressed, until it's up
+	drag_origin: Option<Point2<i32>>,
+	/// Whether the mouse moved further than the drag threshold since the button was pressed
+	dragging: bool,
+	/// Timestamp (of the SDL event, in ms) and position of the last press, to count the consecutive clicks
+	last_press: Option<(u32, Point2<i32>)>,
+}
+
+impl MouseButtonState {
+	const UP: Self = Self { state: KeyState::Up, click_count: 0, drag_origin: None, dragging: false, last_press: None };
+
+	fn update(&mut self) {
+		self.state.update();
+		if self.state.is_up() {
+			self.drag_origin = None;
+			self.dragging = false;
+		}
+	}
+
+	/// The press is consecutive to the last one if it's within 'interval' ms and 'tolerance' pixels
+	fn press(&mut self, position: Point2<i32>, timestamp: u32, interval: u32, tolerance: i32) {
+		let consecutive = self.last_press.is_some_and(|(last_timestamp, last_position)| {
+			timestamp.wrapping_sub(last_timestamp) <= interval && (position - last_position).abs().max() <= tolerance
+		});
+		self.click_count = if consecutive { self.click_count + 1 } else { 1 };
+		self.last_press = Some((timestamp, position));
+		self.state.press();
+		self.drag_origin = Some(position);
+		self.dragging = false;
+	}
+
+	fn release(&mut self) {
+		self.state.release();
+	}
+
+	fn move_to(&mut self, position: Point2<i32>) {
+		if let Some(origin) = self.drag_origin {
+			if !self.dragging && (position - origin).abs().max() > Mouse::DRAG_THRESHOLD {
+				self.dragging = true;
+			}
+		}
+	}
+
+	pub fn get_state(&self) -> KeyState {
+		self.state
+	}
+
+	pub fn is_up(&self) -> bool {
+		self.state.is_up()
+	}
+	pub fn is_pressed(&self) -> bool {
+		self.state.is_pressed()
+	}
+	pub fn is_down(&self) -> bool {
+		self.state.is_down()
+	}
+	pub fn is_released(&self) -> bool {
+		self.state.is_released()
+	}
+
+	/// Pressed for the second time in a row
+	pub fn is_double_pressed(&self) -> bool {
+		self.state.is_pressed() && self.click_count == 2
+	}
+
+	/// Pressed for the third time in a row
+	pub fn is_triple_pressed(&self) -> bool {
+		self.state.is_pressed() && self.click_count == 3
+	}
+
+	/// Number of consecutive clicks of the last press (1 for a simple click, 2 for a double click...)
+	pub fn get_click_count(&self) -> u32 {
+		self.click_count
+	}
+
+	/// Position where the button was pressed, while it's pressed, down or released
+	pub fn get_drag_origin(&self) -> Option<Point2<i32>> {
+		self.drag_origin
+	}
+
+	/// Whether the mouse moved further than the drag threshold since the button was pressed.
+	///
+	/// It stays true the frame the button is released, to tell a drag from a click
+	pub fn is_dragging(&self) -> bool {
+		self.dragging
+	}
+}
+
 pub struct Mouse {
 	pub position: Point2<i32>,
+	/// Movement since the last frame
 	pub delta: Vector2<i32>,
-	// left_button_last_release: Instant,
-	pub left_button: ChadKeyState,
-	pub right_button: KeyState,
-	pub middle_button: KeyState,
-	pub wheel: i32,
+	pub left_button: MouseButtonState,
+	pub right_button: MouseButtonState,
+	pub middle_button: MouseButtonState,
+	/// Back button
+	pub x1_button: MouseButtonState,
+	/// Forward button
+	pub x2_button: MouseButtonState,
+	/// Wheel scroll since the last frame, x to the right and y upwards (whatever the system scroll direction)
+	pub wheel: Vector2<i32>,
+	/// Whether the last mouse event was emulated by SDL from the first finger on the touch screen,
+	/// the widgets take these events as clicks but the touch gestures should be used for the rest
+	pub from_touch: bool,
+	/// Maximum time between two clicks for them to be consecutive (in ms)
+	double_click_interval: u32,
+	/// Maximum distance between two clicks for them to be consecutive (in pixels)
+	double_click_tolerance: i32,
 }
 
 impl Mouse {
-	const TIME_TO_DOUBLE_CLICK: u128 = 100;
+	/// The usual default of the operating systems
+	const DEFAULT_DOUBLE_CLICK_INTERVAL: u32 = 500;
+	const DEFAULT_DOUBLE_CLICK_TOLERANCE: i32 = 4;
+	/// Distance (in pixels) the mouse has to move while a button is held to start a drag
+	const DRAG_THRESHOLD: i32 = 4;
+	/// 'which' of the mouse events emulated from the touch screen (SDL_TOUCH_MOUSEID)
+	const TOUCH_MOUSE_ID: u32 = u32::MAX;
 
 	pub fn new() -> Self {
 		Mouse {
 			position: Point2::origin(),
 			delta: Vector2::zeros(),
-			left_button: ChadKeyState::Up { released_time: Instant::now() },
-			right_button: KeyState::Up,
-			middle_button: KeyState::Up,
-			wheel: 0,
-			// left_button_last_release: Instant::now(),
+			left_button: MouseButtonState::UP,
+			right_button: MouseButtonState::UP,
+			middle_button: MouseButtonState::UP,
+			x1_button: MouseButtonState::UP,
+			x2_button: MouseButtonState::UP,
+			wheel: Vector2::zeros(),
+			from_touch: false,
+			double_click_interval: Self::DEFAULT_DOUBLE_CLICK_INTERVAL,
+			double_click_tolerance: Self::DEFAULT_DOUBLE_CLICK_TOLERANCE,
 		}
 	}
 
+	/// Maximum time between two clicks for them to be consecutive (in ms)
+	pub fn set_double_click_interval(&mut self, interval: u32) {
+		self.double_click_interval = interval;
+	}
+
+	/// Maximum distance between two clicks for them to be consecutive (in pixels)
+	pub fn set_double_click_tolerance(&mut self, tolerance: i32) {
+		self.double_click_tolerance = tolerance;
+	}
+
+	fn buttons_mut(&mut self) -> [&mut MouseButtonState; 5] {
+		[
+			&mut self.left_button,
+			&mut self.right_button,
+			&mut self.middle_button,
+			&mut self.x1_button,
+			&mut self.x2_button,
+		]
+	}
+
 	pub fn update(&mut self) {
 		self.delta = Vector2::zeros();
-		self.wheel = 0;
-		self.left_button.update();
-		self.right_button.update();
-		self.middle_button.update();
+		self.wheel = Vector2::zeros();
+		self.buttons_mut().into_iter().for_each(|button| button.update());
 	}
 
 	pub fn get_event(&mut self, event: sdl2::event::Event) {
 		use sdl2::event::Event;
+		match event {
+			Event::MouseMotion { which, .. }
+			| Event::MouseButtonDown { which, .. }
+			| Event::MouseButtonUp { which, .. }
+			| Event::MouseWheel { which, .. } => self.from_touch = which == Self::TOUCH_MOUSE_ID,
+			_ => {}
+		}
 		match event {
 			Event::MouseMotion { x, y, xrel, yrel, .. } => {
 				self.position = Point2::new(x, y);
-				self.delta = Vector2::new(xrel, yrel);
+				self.delta += Vector2::new(xrel, yrel);
+				self.buttons_mut().into_iter().for_each(|button| button.move_to(Point2::new(x, y)));
+			}
+			Event::MouseButtonDown { timestamp, mouse_btn, x, y, .. } => {
+				// The event timestamps make the click counts independent of the frame rate and replayable
+				let (interval, tolerance) = (self.double_click_interval, self.double_click_tolerance);
+				if let Some(button) = self.get_mut_button(mouse_btn) {
+					button.press(Point2::new(x, y), timestamp, interval, tolerance);
+				}
+			}
+			Event::MouseButtonUp { mouse_btn, .. } => {
+				if let Some(button) = self.get_mut_button(mouse_btn) {
+					button.release();
+				}
 			}
-			Event::MouseButtonDown { mouse_btn, .. } => match mouse_btn {
-				MouseButton::Left => self.left_button.press(),
-				MouseButton::Right => self.right_button = KeyState::Pressed,
-				MouseButton::Middle => self.middle_button = KeyState::Pressed,
-				MouseButton::Unknown | MouseButton::X1 | MouseButton::X2 => todo!(),
-			},
-			Event::MouseButtonUp { mouse_btn, .. } => match mouse_btn {
-				MouseButton::Left => self.left_button.release(),
-				MouseButton::Right => self.right_button = KeyState::Released,
-				MouseButton::Middle => self.middle_button = KeyState::Released,
-				MouseButton::Unknown | MouseButton::X1 | MouseButton::X2 => todo!(),
-			},
-			Event::MouseWheel { y, .. } => {
-				self.wheel = y;
+			Event::MouseWheel { x, y, direction, .. } => {
+				let flip = if direction == MouseWheelDirection::Flipped { -1 } else { 1 };
+				self.wheel += Vector2::new(x, y) * flip;
 			}
 			_ => {}
 		}
 	}
 
+	fn get_mut_button(&mut self, button: MouseButton) -> Option<&mut MouseButtonState> {
+		match button {
+			MouseButton::Left => Some(&mut self.left_button),
+			MouseButton::Right => Some(&mut self.right_button),
+			MouseButton::Middle => Some(&mut self.middle_button),
+			MouseButton::X1 => Some(&mut self.x1_button),
+			MouseButton::X2 => Some(&mut self.x2_button),
+			MouseButton::Unknown => None,
+		}
+	}
+
+	/// State of a button, the unknown buttons are always up
+	pub fn get_button(&self, button: MouseButton) -> &MouseButtonState {
+		match button {
+			MouseButton::Left => &self.left_button,
+			MouseButton::Right => &self.right_button,
+			MouseButton::Middle => &self.middle_button,
+			MouseButton::X1 => &self.x1_button,
+			MouseButton::X2 => &self.x2_button,
+			MouseButton::Unknown => &MouseButtonState::UP,
+		}
+	}
+
 	pub fn left_button_double_clicked(&self) -> bool {
 		self.left_button.is_double_pressed()
-		// self.left_button.is_pressed() && self.left_button_last_release.elapsed().as_millis() < Self::TIME_TO_DOUBLE_CLICK
 	}
 }
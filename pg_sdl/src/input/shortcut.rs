@@ -0,0 +1,331 @@
+use sdl2::keyboard::{Keycode, Mod};
+use std::fmt;
+use std::str::FromStr;
+
+/// Ctrl, Shift, Alt and Gui states of the modifier flags, without distinction between the left and right keys
+pub fn main_modifiers(modifiers: Mod) -> [bool; 4] {
+	[
+		modifiers.intersects(Mod::LCTRLMOD | Mod::RCTRLMOD),
+		modifiers.intersects(Mod::LSHIFTMOD | Mod::RSHIFTMOD),
+		modifiers.intersects(Mod::LALTMOD | Mod::RALTMOD),
+		modifiers.intersects(Mod::LGUIMOD | Mod::RGUIMOD),
+	]
+}
+
+pub fn is_modifier_key(keycode: Keycode) -> bool {
+	matches!(
+		keycode,
+		Keycode::LCtrl
+			| Keycode::RCtrl
+			| Keycode::LShift
+			| Keycode::RShift
+			| Keycode::LAlt
+			| Keycode::RAlt
+			| Keycode::LGui
+			| Keycode::RGui
+	)
+}
+
+/// Names of the modifiers in the shortcuts, in the order of `main_modifiers`
+const MODIFIER_NAMES: [&str; 4] = ["Ctrl", "Shift", "Alt", "Gui"];
+
+fn modifier_from_name(name: &str) -> Option<Mod> {
+	match name.to_lowercase().as_str() {
+		"ctrl" | "control" => Some(Mod::LCTRLMOD | Mod::RCTRLMOD),
+		"shift" => Some(Mod::LSHIFTMOD | Mod::RSHIFTMOD),
+		"alt" | "option" => Some(Mod::LALTMOD | Mod::RALTMOD),
+		"gui" | "cmd" | "super" | "meta" => Some(Mod::LGUIMOD | Mod::RGUIMOD),
+		_ => None,
+	}
+}
+
+/// A key chord is one or several keys pressed together while holding modifiers, like "Ctrl+Shift+Z" or "A+S"
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct KeyChord {
+	modifiers: Mod,
+	keys: Vec<Keycode>,
+}
+
+impl KeyChord {
+	pub fn new(modifiers: Mod, keys: Vec<Keycode>) -> Self {
+		Self { modifiers, keys }
+	}
+
+	pub fn get_modifiers(&self) -> Mod {
+		self.modifiers
+	}
+
+	pub fn get_keys(&self) -> &[Keycode] {
+		&self.keys
+	}
+
+	/// Whether both chords are triggered by the same keys (left and right modifiers are the same)
+	pub fn same_keys(&self, other: &KeyChord) -> bool {
+		main_modifiers(self.modifiers) == main_modifiers(other.modifiers)
+			&& self.keys.len() == other.keys.len()
+			&& self.keys.iter().all(|key| other.keys.contains(key))
+	}
+}
+
+impl fmt::Display for KeyChord {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		let modifiers = main_modifiers(self.modifiers);
+		let names =
+			MODIFIER_NAMES.iter().zip(modifiers).filter(|(_, active)| *active).map(|(name, _)| name.to_string());
+		let names = names.chain(self.keys.iter().map(|key| escape_key_name(&key.name()))).collect::<Vec<String>>();
+		write!(f, "{}", names.join("+"))
+	}
+}
+
+impl KeyChord {
+	/// The chord of the key names of a shortcut, 'text' is the shortcut for the errors
+	fn from_names(names: Vec<String>, text: &str) -> Result<Self, String> {
+		let mut modifiers = Mod::NOMOD;
+		let mut keys = Vec::new();
+		for name in names {
+			if let Some(modifier) = modifier_from_name(&name) {
+				modifiers |= modifier;
+			} else {
+				keys.push(Keycode::from_name(&name).ok_or(format!("Unknown key '{}' in '{}'", name, text))?);
+			}
+		}
+		if keys.is_empty() {
+			return Err(format!("No key in '{}'", text));
+		}
+		Ok(Self::new(modifiers, keys))
+	}
+}
+
+impl FromStr for KeyChord {
+	type Err = String;
+
+	/// Parses a chord like "Ctrl+Shift+Z", the keys are named like SDL names them ("Return", "F1", "Space"...)
+	fn from_str(text: &str) -> Result<Self, Self::Err> {
+		let mut chords = split_chords(text)?;
+		match chords.len() {
+			1 => Self::from_names(chords.remove(0), text),
+			_ => Err(format!("'{}' is not a single chord", text)),
+		}
+	}
+}
+
+/// Whether the character separates the keys, the chords or the shortcuts of an action,
+/// it's escaped with a backslash in the key names (like "Keypad +" or "Left Ctrl")
+fn is_separator(c: char) -> bool {
+	c.is_whitespace() || matches!(c, '+' | '|' | '\\')
+}
+
+fn escape_key_name(name: &str) -> String {
+	let mut escaped = String::new();
+	for c in name.chars() {
+		if is_separator(c) {
+			escaped.push('\\');
+		}
+		escaped.push(c);
+	}
+	escaped
+}
+
+/// Splits the text at the separators that are not escaped with a backslash
+pub(crate) fn split_unescaped(text: &str, separator: char) -> Vec<&str> {
+	let mut parts = Vec::new();
+	let mut start = 0;
+	let mut escaped = false;
+	for (index, c) in text.char_indices() {
+		if escaped {
+			escaped = false;
+		} else if c == '\\' {
+			escaped = true;
+		} else if c == separator {
+			parts.push(&text[start..index]);
+			start = index + c.len_utf8();
+		}
+	}
+	parts.push(&text[start..]);
+	parts
+}
+
+enum Token {
+	Name(String),
+	Plus,
+}
+
+/// Splits a shortcut into its chords, and the chords into their (unescaped) key names.
+/// The chords are separated by spaces, the spaces around the '+' are ignored
+fn split_chords(text: &str) -> Result<Vec<Vec<String>>, String> {
+	let mut tokens = Vec::new();
+	let mut name: Option<String> = None;
+	let mut chars = text.chars();
+	while let Some(c) = chars.next() {
+		if c == '\\' {
+			name.get_or_insert_with(String::new).extend(chars.next());
+		} else if c.is_whitespace() || c == '+' {
+			tokens.extend(name.take().map(Token::Name));
+			if c == '+' {
+				tokens.push(Token::Plus);
+			}
+		} else {
+			name.get_or_insert_with(String::new).push(c);
+		}
+	}
+	tokens.extend(name.map(Token::Name));
+
+	let mut chords: Vec<Vec<String>> = Vec::new();
+	// Whether the last token is a '+', so that the next name is in the same chord
+	let mut joined = false;
+	for token in tokens {
+		match token {
+			Token::Name(name) => {
+				match chords.last_mut() {
+					Some(chord) if joined => chord.push(name),
+					_ => chords.push(vec![name]),
+				}
+				joined = false;
+			}
+			Token::Plus if joined || chords.is_empty() => return Err(format!("Missing key in '{}'", text)),
+			Token::Plus => joined = true,
+		}
+	}
+	if joined {
+		return Err(format!("Missing key in '{}'", text));
+	}
+	Ok(chords)
+}
+
+/// A shortcut is a sequence of key chords pressed one after the other, like "Ctrl+K Ctrl+C".
+///
+/// Most shortcuts are a single chord, like "Ctrl+Shift+Z".
+/// Shortcuts can be parsed from strings (`"Ctrl+Shift+Z".parse()`) and written back with `to_string`
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Shortcut {
+	chords: Vec<KeyChord>,
+}
+
+impl Shortcut {
+	pub fn new(modifiers: Mod, key: Keycode) -> Self {
+		Self::from_chords(vec![KeyChord::new(modifiers, vec![key])])
+	}
+
+	pub fn from_chords(chords: Vec<KeyChord>) -> Self {
+		Self { chords }
+	}
+
+	pub fn get_chords(&self) -> &[KeyChord] {
+		&self.chords
+	}
+
+	/// Whether both shortcuts are triggered by the same key press (when one ends like the other)
+	pub fn conflicts_with(&self, other: &Shortcut) -> bool {
+		self.chords.iter().rev().zip(other.chords.iter().rev()).all(|(chord, other_chord)| chord.same_keys(other_chord))
+	}
+
+	#[allow(non_snake_case)]
+	pub fn COPY() -> Self {
+		Self::new(Mod::LCTRLMOD, Keycode::C)
+	}
+
+	#[allow(non_snake_case)]
+	pub fn PASTE() -> Self {
+		Self::new(Mod::LCTRLMOD, Keycode::V)
+	}
+
+	#[allow(non_snake_case)]
+	pub fn CUT() -> Self {
+		Self::new(Mod::LCTRLMOD, Keycode::X)
+	}
+
+	#[allow(non_snake_case)]
+	pub fn SELECT_ALL() -> Self {
+		Self::new(Mod::LCTRLMOD, Keycode::A)
+	}
+
+	#[allow(non_snake_case)]
+	pub fn UNDO() -> Self {
+		Self::new(Mod::LCTRLMOD, Keycode::Z)
+	}
+
+	#[allow(non_snake_case)]
+	pub fn REDO() -> Self {
+		Self::new(Mod::LCTRLMOD, Keycode::Y)
+	}
+}
+
+impl fmt::Display for Shortcut {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		let chords = self.chords.iter().map(|chord| chord.to_string()).collect::<Vec<String>>();
+		write!(f, "{}", chords.join(" "))
+	}
+}
+
+impl FromStr for Shortcut {
+	type Err = String;
+
+	/// Parses a shortcut like "Ctrl+Shift+Z", or a sequence of chords separated by spaces like "Ctrl+K Ctrl+C"
+	fn from_str(text: &str) -> Result<Self, Self::Err> {
+		let chords = split_chords(text)?
+			.into_iter()
+			.map(|names| KeyChord::from_names(names, text))
+			.collect::<Result<Vec<KeyChord>, String>>()?;
+		if chords.is_empty() {
+			return Err("Empty shortcut".to_string());
+		}
+		Ok(Self::from_chords(chords))
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn round_trip() {
+		for text in
+			["Ctrl+Shift+Z", "A+S", "Ctrl+K Ctrl+C", "Alt+F4", "Gui+Return", "K A+S", r"Ctrl+Keypad\ 1", r"Keypad\ \+"]
+		{
+			let shortcut = text.parse::<Shortcut>().unwrap();
+			assert_eq!(shortcut.to_string(), text);
+			assert_eq!(shortcut.to_string().parse::<Shortcut>(), Ok(shortcut));
+		}
+	}
+
+	#[test]
+	fn modifier_aliases_and_order() {
+		let shortcut = "shift + control + z".parse::<Shortcut>().unwrap();
+		assert_eq!(shortcut.to_string(), "Ctrl+Shift+Z");
+		assert_eq!("Cmd+Option+P".parse::<Shortcut>().unwrap().to_string(), "Alt+Gui+P");
+	}
+
+	#[test]
+	fn key_names_with_separators() {
+		for keycode in
+			[Keycode::Kp1, Keycode::KpPlus, Keycode::Plus, Keycode::KpVerticalBar, Keycode::LCtrl, Keycode::Backslash]
+		{
+			let shortcut = Shortcut::from_chords(vec![
+				KeyChord::new(Mod::LSHIFTMOD | Mod::RSHIFTMOD, vec![keycode]),
+				KeyChord::new(Mod::NOMOD, vec![Keycode::C]),
+			]);
+			assert_eq!(shortcut.to_string().parse::<Shortcut>(), Ok(shortcut));
+		}
+		assert_eq!("Keypad\\ 1".parse::<KeyChord>(), Ok(KeyChord::new(Mod::NOMOD, vec![Keycode::Kp1])));
+	}
+
+	#[test]
+	fn parse_errors() {
+		assert!("".parse::<Shortcut>().is_err());
+		assert!("Ctrl+Shift".parse::<Shortcut>().is_err());
+		assert!("Ctrl+NotAKey".parse::<Shortcut>().is_err());
+		assert!("Ctrl+ K".parse::<Shortcut>().is_ok());
+		assert!("Ctrl+".parse::<Shortcut>().is_err());
+		assert!("+A".parse::<Shortcut>().is_err());
+		assert!("Ctrl+K Ctrl+C".parse::<KeyChord>().is_err());
+	}
+
+	#[test]
+	fn conflicts() {
+		let ctrl_z = "Ctrl+Z".parse::<Shortcut>().unwrap();
+		assert!(ctrl_z.conflicts_with(&Shortcut::UNDO()));
+		assert!(ctrl_z.conflicts_with(&"Ctrl+K Ctrl+Z".parse().unwrap()));
+		assert!(!ctrl_z.conflicts_with(&"Ctrl+Shift+Z".parse().unwrap()));
+		assert!("A+S".parse::<Shortcut>().unwrap().conflicts_with(&"S+A".parse().unwrap()));
+	}
+}
@@ -0,0 +1,392 @@
+use nalgebra::{Point2, Vector2};
+use sdl2::event::Event;
+use std::f64::consts::PI;
+
+/// A finger on the touch screen, its positions are normalized between 0 and 1 over the window
+#[derive(Clone, PartialEq, Debug)]
+pub struct TouchPoint {
+	pub id: i64,
+	pub position: Point2<f64>,
+	/// Position at the start of the frame
+	pub previous_position: Point2<f64>,
+	pub start_position: Point2<f64>,
+	pub pressure: f64,
+	/// Timestamp (of the SDL event, in ms) when the finger touched the screen
+	pub start_time: u32,
+}
+
+impl TouchPoint {
+	pub fn delta(&self) -> Vector2<f64> {
+		self.position - self.previous_position
+	}
+}
+
+/// A gesture recognized during the frame, the positions are normalized like the touch points
+#[derive(Clone, PartialEq, Debug)]
+pub enum Gesture {
+	/// A finger touched the screen briefly without moving
+	Tap { position: Point2<f64> },
+	/// A finger stayed on the screen without moving (sent once)
+	LongPress { position: Point2<f64> },
+	/// Fingers moved together, 'delta' is the movement of their center
+	Pan { delta: Vector2<f64>, fingers: usize },
+	/// Two fingers moved apart (scale > 1) or closer (scale < 1)
+	Pinch { center: Point2<f64>, scale: f64 },
+	/// Two fingers turned around their center (in radians, clockwise)
+	Rotate { center: Point2<f64>, angle: f64 },
+}
+
+/// Sum of the SDL multigesture events of a frame
+#[derive(Clone, Copy, PartialEq, Debug)]
+struct MultiGesture {
+	center: Point2<f64>,
+	/// Rotation of the fingers around their center (in radians, clockwise)
+	angle: f64,
+	/// Change of the mean distance of the fingers to their center (normalized)
+	distance: f64,
+}
+
+/// The fingers on the touch screen and the gestures they make.
+///
+/// The gestures are recognized from the finger events, so they can be tested with synthetic events
+/// sent to `Input::handle_event`. With several fingers, the pinches and rotations come from the SDL multigesture
+/// events (which follow every finger), or from the first two fingers when SDL doesn't send them
+pub struct Touch {
+	points: Vec<TouchPoint>,
+	gestures: Vec<Gesture>,
+	multi_gesture: Option<MultiGesture>,
+	/// Whether the current touch (since the first finger went down) moved, got several fingers or long-pressed
+	/// (it can't be a tap anymore)
+	touch_consumed: bool,
+	long_pressed: bool,
+}
+
+impl Default for Touch {
+	fn default() -> Self {
+		Self::new()
+	}
+}
+
+impl Touch {
+	/// Duration after which a still finger makes a long press
+	const LONG_PRESS_MS: u32 = 500;
+	/// Distance (normalized) a finger has to move to not be still anymore
+	const MOVE_THRESHOLD: f64 = 0.01;
+
+	pub fn new() -> Self {
+		Self {
+			points: Vec::new(),
+			gestures: Vec::new(),
+			multi_gesture: None,
+			touch_consumed: false,
+			long_pressed: false,
+		}
+	}
+
+	/// Should be called every frame, before the events are handled
+	pub fn update(&mut self) {
+		self.gestures.clear();
+		self.multi_gesture = None;
+		self.points.iter_mut().for_each(|point| point.previous_position = point.position);
+	}
+
+	pub fn get_event(&mut self, event: &Event) {
+		match event {
+			Event::FingerDown { timestamp, finger_id, x, y, pressure, .. } => {
+				if self.points.is_empty() {
+					self.touch_consumed = false;
+					self.long_pressed = false;
+				} else {
+					self.touch_consumed = true;
+				}
+				let position = Point2::new(*x as f64, *y as f64);
+				self.points.push(TouchPoint {
+					id: *finger_id,
+					position,
+					previous_position: position,
+					start_position: position,
+					pressure: *pressure as f64,
+					start_time: *timestamp,
+				});
+			}
+			Event::FingerMotion { finger_id, x, y, pressure, .. } => {
+				if let Some(point) = self.points.iter_mut().find(|point| point.id == *finger_id) {
+					point.position = Point2::new(*x as f64, *y as f64);
+					point.pressure = *pressure as f64;
+					if (point.position - point.start_position).norm() > Self::MOVE_THRESHOLD {
+						self.touch_consumed = true;
+					}
+				}
+			}
+			Event::FingerUp { timestamp, finger_id, x, y, .. } => {
+				if let Some(index) = self.points.iter().position(|point| point.id == *finger_id) {
+					let point = self.points.remove(index);
+					if !self.touch_consumed && timestamp.wrapping_sub(point.start_time) < Self::LONG_PRESS_MS {
+						self.gestures.push(Gesture::Tap { position: Point2::new(*x as f64, *y as f64) });
+					}
+				}
+			}
+			Event::MultiGesture { d_theta, d_dist, x, y, .. } => {
+				let (angle, distance) =
+					self.multi_gesture.map_or((0.0, 0.0), |gesture| (gesture.angle, gesture.distance));
+				self.multi_gesture = Some(MultiGesture {
+					center: Point2::new(*x as f64, *y as f64),
+					angle: angle + *d_theta as f64,
+					distance: distance + *d_dist as f64,
+				});
+			}
+			_ => {}
+		}
+	}
+
+	/// Recognizes the continuous gestures, should be called every frame after the events are handled.
+	///
+	/// 'time' is the frame clock, on the clock of the event timestamps (`Input::time` follows the wall clock,
+	/// a replay can pass the time of its frames instead)
+	pub fn recognize_gestures(&mut self, time: u32) {
+		match self.points.as_slice() {
+			[point] => {
+				if !self.touch_consumed
+					&& !self.long_pressed
+					&& time.wrapping_sub(point.start_time) >= Self::LONG_PRESS_MS
+				{
+					self.long_pressed = true;
+					self.touch_consumed = true;
+					self.gestures.push(Gesture::LongPress { position: point.position });
+				}
+				if self.touch_consumed && !self.long_pressed && point.delta() != Vector2::zeros() {
+					self.gestures.push(Gesture::Pan { delta: point.delta(), fingers: 1 });
+				}
+			}
+			[first, second, ..] => {
+				let fingers = self.points.len();
+				let delta = self.points.iter().map(|point| point.delta()).sum::<Vector2<f64>>() / fingers as f64;
+				if delta != Vector2::zeros() {
+					self.gestures.push(Gesture::Pan { delta, fingers });
+				}
+
+				let gestures = match self.multi_gesture {
+					Some(gesture) => Self::multi_gestures(&self.points, gesture),
+					None => Self::two_finger_gestures(first, second),
+				};
+				self.gestures.extend(gestures);
+			}
+			[] => {}
+		}
+	}
+
+	/// Pinch and rotation of all the fingers, from the SDL multigesture events
+	fn multi_gestures(points: &[TouchPoint], gesture: MultiGesture) -> Vec<Gesture> {
+		let mut gestures = Vec::new();
+		// Mean distance of the fingers to their center, after and before the events of the frame
+		let center = points.iter().map(|point| point.position.coords).sum::<Vector2<f64>>() / points.len() as f64;
+		let distance =
+			points.iter().map(|point| (point.position.coords - center).norm()).sum::<f64>() / points.len() as f64;
+		let previous_distance = distance - gesture.distance;
+		if gesture.distance != 0.0 && previous_distance > 0.0 && distance > 0.0 {
+			gestures.push(Gesture::Pinch { center: gesture.center, scale: distance / previous_distance });
+		}
+		if gesture.angle != 0.0 {
+			gestures.push(Gesture::Rotate { center: gesture.center, angle: gesture.angle });
+		}
+		gestures
+	}
+
+	/// Pinch and rotation of the first two fingers
+	fn two_finger_gestures(first: &TouchPoint, second: &TouchPoint) -> Vec<Gesture> {
+		let mut gestures = Vec::new();
+		let center = Point2::from((first.position.coords + second.position.coords) / 2.0);
+		let (previous, current) =
+			(second.previous_position - first.previous_position, second.position - first.position);
+		if previous.norm() > 0.0 && current.norm() > 0.0 {
+			let scale = current.norm() / previous.norm();
+			if scale != 1.0 {
+				gestures.push(Gesture::Pinch { center, scale });
+			}
+			let mut angle = current.y.atan2(current.x) - previous.y.atan2(previous.x);
+			if angle > PI {
+				angle -= 2.0 * PI;
+			} else if angle < -PI {
+				angle += 2.0 * PI;
+			}
+			if angle != 0.0 {
+				gestures.push(Gesture::Rotate { center, angle });
+			}
+		}
+		gestures
+	}
+
+	pub fn points(&self) -> &[TouchPoint] {
+		&self.points
+	}
+
+	/// The gestures recognized this frame
+	pub fn gestures(&self) -> &[Gesture] {
+		&self.gestures
+	}
+
+	pub fn tapped(&self) -> Option<Point2<f64>> {
+		self.gestures.iter().find_map(|gesture| match gesture {
+			Gesture::Tap { position } => Some(*position),
+			_ => None,
+		})
+	}
+
+	pub fn long_pressed(&self) -> Option<Point2<f64>> {
+		self.gestures.iter().find_map(|gesture| match gesture {
+			Gesture::LongPress { position } => Some(*position),
+			_ => None,
+		})
+	}
+
+	/// Movement of the fingers if exactly 'fingers' fingers are panning
+	pub fn pan(&self, fingers: usize) -> Option<Vector2<f64>> {
+		self.gestures.iter().find_map(|gesture| match gesture {
+			Gesture::Pan { delta, fingers: pan_fingers } if *pan_fingers == fingers => Some(*delta),
+			_ => None,
+		})
+	}
+
+	/// Center and scale of the pinch
+	pub fn pinch(&self) -> Option<(Point2<f64>, f64)> {
+		self.gestures.iter().find_map(|gesture| match gesture {
+			Gesture::Pinch { center, scale } => Some((*center, *scale)),
+			_ => None,
+		})
+	}
+
+	/// Center and angle of the rotation
+	pub fn rotation(&self) -> Option<(Point2<f64>, f64)> {
+		self.gestures.iter().find_map(|gesture| match gesture {
+			Gesture::Rotate { center, angle } => Some((*center, *angle)),
+			_ => None,
+		})
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn down(timestamp: u32, finger_id: i64, x: f32, y: f32) -> Event {
+		Event::FingerDown { timestamp, touch_id: 0, finger_id, x, y, dx: 0.0, dy: 0.0, pressure: 1.0 }
+	}
+
+	fn motion(timestamp: u32, finger_id: i64, x: f32, y: f32) -> Event {
+		Event::FingerMotion { timestamp, touch_id: 0, finger_id, x, y, dx: 0.0, dy: 0.0, pressure: 1.0 }
+	}
+
+	fn up(timestamp: u32, finger_id: i64, x: f32, y: f32) -> Event {
+		Event::FingerUp { timestamp, touch_id: 0, finger_id, x, y, dx: 0.0, dy: 0.0, pressure: 0.0 }
+	}
+
+	/// Handles the events of a frame like `Input::get_events`
+	fn frame(touch: &mut Touch, time: u32, events: &[Event]) {
+		touch.update();
+		events.iter().for_each(|event| touch.get_event(event));
+		touch.recognize_gestures(time);
+	}
+
+	fn assert_close(a: f64, b: f64) {
+		assert!((a - b).abs() < 1e-6, "{} != {}", a, b);
+	}
+
+	#[test]
+	fn tap() {
+		let mut touch = Touch::new();
+		frame(&mut touch, 0, &[down(0, 1, 0.5, 0.25)]);
+		assert_eq!(touch.points().len(), 1);
+		assert_eq!(touch.tapped(), None);
+		frame(&mut touch, 100, &[up(100, 1, 0.5, 0.25)]);
+		assert_eq!(touch.tapped(), Some(Point2::new(0.5, 0.25)));
+		assert!(touch.points().is_empty());
+	}
+
+	#[test]
+	fn long_press_is_not_a_tap() {
+		let mut touch = Touch::new();
+		frame(&mut touch, 0, &[down(0, 1, 0.5, 0.5)]);
+		frame(&mut touch, Touch::LONG_PRESS_MS, &[]);
+		assert_eq!(touch.long_pressed(), Some(Point2::new(0.5, 0.5)));
+		frame(&mut touch, Touch::LONG_PRESS_MS + 20, &[]);
+		assert_eq!(touch.long_pressed(), None);
+		frame(&mut touch, Touch::LONG_PRESS_MS + 40, &[up(Touch::LONG_PRESS_MS + 40, 1, 0.5, 0.5)]);
+		assert_eq!(touch.tapped(), None);
+	}
+
+	#[test]
+	fn one_finger_pan() {
+		let mut touch = Touch::new();
+		frame(&mut touch, 0, &[down(0, 1, 0.5, 0.5)]);
+		frame(&mut touch, 20, &[motion(20, 1, 0.5, 0.6)]);
+		let delta = touch.pan(1).unwrap();
+		assert_close(delta.x, 0.0);
+		assert_close(delta.y, 0.1);
+		assert_eq!(touch.pan(2), None);
+		frame(&mut touch, 40, &[up(40, 1, 0.5, 0.6)]);
+		assert_eq!(touch.tapped(), None);
+	}
+
+	#[test]
+	fn two_finger_pan_pinch_and_rotation() {
+		let mut touch = Touch::new();
+		frame(&mut touch, 0, &[down(0, 1, 0.4, 0.5), down(0, 2, 0.6, 0.5)]);
+
+		frame(&mut touch, 20, &[motion(20, 1, 0.4, 0.6), motion(20, 2, 0.6, 0.6)]);
+		let delta = touch.pan(2).unwrap();
+		assert_close(delta.x, 0.0);
+		assert_close(delta.y, 0.1);
+		assert_eq!(touch.pinch(), None);
+		assert_eq!(touch.rotation(), None);
+
+		frame(&mut touch, 40, &[motion(40, 1, 0.3, 0.6), motion(40, 2, 0.7, 0.6)]);
+		let (center, scale) = touch.pinch().unwrap();
+		assert_close(center.x, 0.5);
+		assert_close(center.y, 0.6);
+		assert_close(scale, 2.0);
+
+		// A quarter turn, clockwise on the screen (y goes down)
+		frame(&mut touch, 60, &[motion(60, 1, 0.5, 0.4), motion(60, 2, 0.5, 0.8)]);
+		let (_, angle) = touch.rotation().unwrap();
+		assert_close(angle, PI / 2.0);
+
+		frame(&mut touch, 80, &[up(80, 1, 0.5, 0.4), up(80, 2, 0.5, 0.8)]);
+		assert!(touch.points().is_empty());
+		assert_eq!(touch.tapped(), None);
+	}
+
+	#[test]
+	fn multigesture_events() {
+		let mut touch = Touch::new();
+		frame(&mut touch, 0, &[down(0, 1, 0.4, 0.5), down(0, 2, 0.6, 0.5), down(0, 3, 0.5, 0.3)]);
+		let gesture = |d_theta: f32, d_dist: f32| Event::MultiGesture {
+			timestamp: 20,
+			touch_id: 0,
+			d_theta,
+			d_dist,
+			x: 0.5,
+			y: 0.45,
+			num_fingers: 3,
+		};
+		frame(&mut touch, 20, &[motion(20, 3, 0.5, 0.2), gesture(0.1, 0.02), gesture(0.05, 0.01)]);
+
+		let (center, angle) = touch.rotation().unwrap();
+		assert_close(center.x, 0.5);
+		assert_close(center.y, 0.45);
+		assert_close(angle, 0.15);
+
+		// The scale is the one of the mean distance of the three fingers to their center
+		let centroid = Vector2::new(0.5, 0.4);
+		let distance = [Vector2::new(0.4, 0.5), Vector2::new(0.6, 0.5), Vector2::new(0.5, 0.2)]
+			.iter()
+			.map(|position| (position - centroid).norm())
+			.sum::<f64>()
+			/ 3.0;
+		let (_, scale) = touch.pinch().unwrap();
+		assert_close(scale, distance / (distance - 0.03));
+
+		// Without multigesture events, the pinch of the first two fingers is used
+		frame(&mut touch, 40, &[motion(40, 1, 0.3, 0.5), motion(40, 2, 0.7, 0.5)]);
+		assert_close(touch.pinch().unwrap().1, 2.0);
+	}
+}
@@ -25,14 +25,18 @@ pub mod widgets;
 pub mod prelude {
 	pub use crate::app::{App, PgSdl};
 	pub use crate::camera::Camera;
-	pub use crate::color::{darker, hsv_color, paler, Colors};
+	pub use crate::color::{color_to_hex, color_to_hsv, darker, hex_to_color, hsv_color, paler, Colors};
 	pub use crate::draw_circle::{draw_circle, fill_circle};
 	pub use crate::input::Input;
 	pub use crate::point;
 	pub use crate::rect;
 	pub use crate::style::Align;
 	pub use crate::text::{TextDrawer, TextStyle};
-	pub use crate::widgets::{Button, Orientation, Slider, SliderType, TextInput, TextInputStyle, Widget, Widgets};
+	pub use crate::widgets::{
+		Binding, Button, ColorPicker, Dialog, DialogResult, NumberInput, NumberType, Orientation, Progress,
+		ProgressBar, Slider, SliderType, Spinner, TextArea, TextInput, TextInputStyle, Validator, Widget, WidgetEvent,
+		WidgetValue, Widgets,
+	};
 	pub use sdl2::{
 		self,
 		gfx::primitives::DrawRenderer,
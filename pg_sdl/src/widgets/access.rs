@@ -0,0 +1,189 @@
+use sdl2::rect::Rect;
+use std::fmt;
+
+/// What a widget is, for the assistive technologies
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Role {
+	Window,
+	Button,
+	Switch,
+	Slider,
+	TextInput,
+	TextArea,
+	NumberInput,
+	ColorPicker,
+	ProgressBar,
+	Spinner,
+	Dialog,
+	Label,
+	Unknown,
+}
+
+/// A widget as described to the assistive technologies (like the screen readers)
+#[derive(Clone, PartialEq, Debug)]
+pub struct AccessNode {
+	/// Path of the widget in `Widgets`, like "dialog/ok" (filled by `Widgets::access_tree`)
+	pub id: String,
+	pub role: Role,
+	/// Text announced by the screen readers, it's the name of the widget if it's empty
+	pub label: String,
+	pub value: Option<String>,
+	/// Some for the widgets that are switched on or off
+	pub checked: Option<bool>,
+	pub pressed: bool,
+	pub focused: bool,
+	pub disabled: bool,
+	/// Whether the widget is working (like a running spinner)
+	pub busy: bool,
+	/// Whether the widget blocks the inputs to the other widgets
+	pub modal: bool,
+	/// Position in the window (in pixels)
+	pub bounds: Option<Rect>,
+	pub children: Vec<AccessNode>,
+}
+
+impl AccessNode {
+	pub fn new(role: Role, label: &str) -> Self {
+		AccessNode {
+			id: String::new(),
+			role,
+			label: label.to_string(),
+			value: None,
+			checked: None,
+			pressed: false,
+			focused: false,
+			disabled: false,
+			busy: false,
+			modal: false,
+			bounds: None,
+			children: Vec::new(),
+		}
+	}
+
+	/// Finds a node of the subtree by its id
+	pub fn find(&self, id: &str) -> Option<&AccessNode> {
+		if self.id == id {
+			return Some(self);
+		}
+		self.children.iter().find_map(|child| child.find(id))
+	}
+
+	/// Prefixes the ids of the children with the id of their parent, recursively
+	pub(crate) fn assign_child_ids(&mut self) {
+		for child in &mut self.children {
+			child.id = if self.id.is_empty() { child.id.clone() } else { format!("{}/{}", self.id, child.id) };
+			child.assign_child_ids();
+		}
+	}
+
+	fn write_indented(&self, f: &mut fmt::Formatter<'_>, depth: usize) -> fmt::Result {
+		write!(f, "{}{:?}", "  ".repeat(depth), self.role)?;
+		if !self.id.is_empty() {
+			write!(f, " #{}", self.id)?;
+		}
+		write!(f, " {:?}", self.label)?;
+		if let Some(value) = &self.value {
+			write!(f, " = {:?}", value)?;
+		}
+		match self.checked {
+			Some(true) => write!(f, " [checked]")?,
+			Some(false) => write!(f, " [unchecked]")?,
+			None => {}
+		}
+		for (flag, name) in [
+			(self.pressed, "pressed"),
+			(self.focused, "focused"),
+			(self.disabled, "disabled"),
+			(self.busy, "busy"),
+			(self.modal, "modal"),
+		] {
+			if flag {
+				write!(f, " [{}]", name)?;
+			}
+		}
+		if let Some(bounds) = self.bounds {
+			write!(f, " ({}, {}, {}x{})", bounds.x(), bounds.y(), bounds.width(), bounds.height())?;
+		}
+		writeln!(f)?;
+		self.children.iter().try_for_each(|child| child.write_indented(f, depth + 1))
+	}
+}
+
+/// Text dump of the tree, one node per line indented by depth, like:
+///
+/// ```text
+/// Window ""
+///   Button #reset "Reset" [focused] (750, 35, 120x50)
+///   Slider #speed "speed" = "0.50" (750, 100, 200x30)
+/// ```
+impl fmt::Display for AccessNode {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		self.write_indented(f, 0)
+	}
+}
+
+/// Exposes the accessibility tree to the system (for example through an AccessKit adapter, AT-SPI on Linux)
+pub trait AccessBackend {
+	/// Called with the whole tree each time it changes
+	fn update_tree(&mut self, tree: &AccessNode);
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn node(id: &str, role: Role, label: &str) -> AccessNode {
+		AccessNode { id: id.to_string(), ..AccessNode::new(role, label) }
+	}
+
+	/// A window with a slider and a dialog, like `Widgets::access_tree` builds it
+	fn tree() -> AccessNode {
+		let mut dialog = AccessNode {
+			modal: true,
+			bounds: Some(Rect::new(420, 280, 360, 140)),
+			..node("reset dialog", Role::Dialog, "Reset")
+		};
+		dialog.children = vec![
+			node("message", Role::Label, "Reset the simulation ?"),
+			AccessNode { focused: true, ..node("ok", Role::Button, "OK") },
+			AccessNode { disabled: true, ..node("cancel", Role::Button, "Cancel") },
+		];
+		dialog.assign_child_ids();
+		let slider = AccessNode {
+			value: Some("0.50".to_string()),
+			bounds: Some(Rect::new(500, 50, 200, 32)),
+			..node("speed", Role::Slider, "speed")
+		};
+		let switch = AccessNode { checked: Some(false), busy: true, ..node("switch", Role::Switch, "Forces") };
+		AccessNode { children: vec![slider, switch, dialog], ..AccessNode::new(Role::Window, "") }
+	}
+
+	#[test]
+	fn text_dump() {
+		let expected = "\
+Window \"\"
+  Slider #speed \"speed\" = \"0.50\" (500, 50, 200x32)
+  Switch #switch \"Forces\" [unchecked] [busy]
+  Dialog #reset dialog \"Reset\" [modal] (420, 280, 360x140)
+    Label #reset dialog/message \"Reset the simulation ?\"
+    Button #reset dialog/ok \"OK\" [focused]
+    Button #reset dialog/cancel \"Cancel\" [disabled]
+";
+		assert_eq!(tree().to_string(), expected);
+	}
+
+	#[test]
+	fn child_ids_are_paths() {
+		let tree = tree();
+		assert_eq!(tree.find("reset dialog/ok").map(|node| node.label.as_str()), Some("OK"));
+		assert_eq!(tree.find("ok"), None);
+
+		// The ids of the grandchildren are prefixed by every ancestor
+		let mut root = node("a", Role::Unknown, "");
+		let mut child = node("b", Role::Unknown, "");
+		child.children.push(node("c", Role::Unknown, ""));
+		root.children.push(child);
+		root.assign_child_ids();
+		assert!(root.find("a/b/c").is_some());
+	}
+}
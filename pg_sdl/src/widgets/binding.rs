@@ -0,0 +1,82 @@
+use std::cell::RefCell;
+use std::rc::Rc;
+
+struct Shared<T> {
+	value: T,
+	/// Incremented at each change, so that the widgets notice the changes of the app
+	version: u64,
+}
+
+/// A value shared between the app and widgets, the changes flow both ways.
+///
+/// The app keeps a clone and reads or sets the value, the widget bound to it (`set_binding`) writes the changes of
+/// the user to it and shows the changes of the app from its next update
+pub struct Binding<T> {
+	shared: Rc<RefCell<Shared<T>>>,
+}
+
+impl<T> Clone for Binding<T> {
+	fn clone(&self) -> Self {
+		Binding { shared: Rc::clone(&self.shared) }
+	}
+}
+
+impl<T: Clone + PartialEq> Binding<T> {
+	pub fn new(value: T) -> Self {
+		Binding { shared: Rc::new(RefCell::new(Shared { value, version: 0 })) }
+	}
+
+	pub fn get(&self) -> T {
+		self.shared.borrow().value.clone()
+	}
+
+	/// Reads the value without cloning it
+	pub fn with<R>(&self, f: impl FnOnce(&T) -> R) -> R {
+		f(&self.shared.borrow().value)
+	}
+
+	/// Sets the value, the version changes only if the value does
+	pub fn set(&self, value: T) {
+		let mut shared = self.shared.borrow_mut();
+		if shared.value != value {
+			shared.value = value;
+			shared.version += 1;
+		}
+	}
+
+	pub fn get_version(&self) -> u64 {
+		self.shared.borrow().version
+	}
+}
+
+/// The binding of a widget, with the version it last synchronized with
+pub(crate) struct BoundValue<T> {
+	binding: Binding<T>,
+	version: u64,
+}
+
+impl<T: Clone + PartialEq> BoundValue<T> {
+	/// A widget binding that has not synchronized yet, so that the widget takes the value of the app first
+	pub(crate) fn new(binding: Binding<T>) -> Self {
+		let version = binding.get_version().wrapping_sub(1);
+		BoundValue { binding, version }
+	}
+
+	/// The value set by the app since the last synchronization, if it changed
+	pub(crate) fn pull(&mut self) -> Option<T> {
+		let version = self.binding.get_version();
+		if version == self.version {
+			return None;
+		}
+		self.version = version;
+		Some(self.binding.get())
+	}
+
+	/// Writes the value of the widget to the binding, it's cloned only if it changed
+	pub(crate) fn push(&mut self, value: &T) {
+		if !self.binding.with(|bound| bound == value) {
+			self.binding.set(value.clone());
+		}
+		self.version = self.binding.get_version();
+	}
+}
@@ -2,10 +2,10 @@ use crate::canvas::{draw_rect, draw_rounded_rect, fill_rect, fill_rounded_rect};
 use crate::prelude::*;
 use crate::{
 	color::{darker, Colors},
-	input::{Input, KeyState},
+	input::{Input, KeyState, MouseCursor},
 	text::TextDrawer,
 	widgets::Widget,
-	widgets::{HOVER, PUSH},
+	widgets::{activation_state, AccessNode, Role, WidgetEvent, HOVER, PUSH},
 };
 use sdl2::pixels::Color;
 use sdl2::rect::Rect;
@@ -22,6 +22,7 @@ pub struct Button {
 	text_style: TextStyle,
 	text: String,
 	hovered: bool,
+	focused: bool,
 	pub state: KeyState,
 }
 
@@ -36,6 +37,7 @@ impl Button {
 			text_style,
 			text,
 			hovered: false,
+			focused: false,
 			state: KeyState::new(),
 		}
 	}
@@ -56,10 +58,12 @@ impl Widget for Button {
 			changed = true;
 		}
 
-		if input.mouse.left_button.is_pressed() && self.hovered {
+		// The focused button is also pushed with Enter, Space or the gamepad
+		let activation = if self.focused { activation_state(input) } else { KeyState::Up };
+		if (input.mouse.left_button.is_pressed() && self.hovered) || activation.is_pressed() {
 			self.state.press();
 			changed = true;
-		} else if self.state.is_down() && input.mouse.left_button.is_released() {
+		} else if self.state.is_down() && (input.mouse.left_button.is_released() || activation.is_released()) {
 			self.state.release();
 			changed = true;
 		}
@@ -86,4 +90,48 @@ impl Widget for Button {
 
 		text_drawer.draw(canvas, self.rect.center(), &self.text_style, &self.text, Align::Center);
 	}
+
+	fn is_hovered(&self) -> bool {
+		self.hovered
+	}
+
+	fn is_focusable(&self) -> bool {
+		true
+	}
+
+	fn is_focused(&self) -> bool {
+		self.focused
+	}
+
+	fn set_focused(&mut self, focused: bool) {
+		self.focused = focused;
+	}
+
+	fn get_rect(&self) -> Option<Rect> {
+		Some(self.rect)
+	}
+
+	fn access_node(&self) -> Option<AccessNode> {
+		Some(AccessNode {
+			pressed: self.state.is_pressed() || self.state.is_down(),
+			..AccessNode::new(Role::Button, &self.text)
+		})
+	}
+
+	fn get_cursor(&self) -> Option<MouseCursor> {
+		self.hovered.then_some(MouseCursor::Hand)
+	}
+
+	fn reset_interaction(&mut self) {
+		self.hovered = false;
+		self.state = KeyState::Up;
+	}
+
+	fn get_events(&self) -> Vec<WidgetEvent> {
+		if self.state.is_pressed() {
+			vec![WidgetEvent::Clicked]
+		} else {
+			Vec::new()
+		}
+	}
 }
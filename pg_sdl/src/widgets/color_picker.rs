@@ -0,0 +1,412 @@
+use crate::canvas::{draw_rect, fill_rect};
+use crate::color::{color_to_hex, color_to_hsv, hex_to_color};
+use crate::input::MouseCursor;
+use crate::prelude::*;
+use crate::widgets::{AccessNode, DragPreview, Role, WidgetValue};
+use sdl2::render::BlendMode;
+use std::any::Any;
+
+/// Which part of the color picker is being dragged
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+enum Dragging {
+	SaturationValue,
+	Hue,
+	Alpha,
+}
+
+/// A color picker is a widget to choose a color, it has:
+///
+/// a **saturation / value** square and a **hue** bar
+///
+/// an **alpha** bar
+///
+/// a **hexadecimal** text field ("#RRGGBB" or "#RRGGBBAA")
+///
+/// a **history** of the last picked colors, that can be clicked to pick them again
+///
+/// The color can be dragged from its preview, and colors can be dropped on the color picker
+pub struct ColorPicker {
+	rect: Rect,
+	hue: u16,
+	saturation: f32,
+	value: f32,
+	alpha: u8,
+	hex_input: TextInput,
+	history: Vec<Color>,
+	dragging: Option<Dragging>,
+	hovered: bool,
+	/// Whether the color was dragged out of the preview since the left button was pressed
+	color_dragged: bool,
+	/// Color dragged out this frame, until `Widgets` takes the drag
+	drag: Option<Color>,
+}
+
+impl ColorPicker {
+	const MARGIN: i32 = 8;
+	const BAR_THICKNESS: u32 = 16;
+	const INPUT_HEIGHT: u32 = 26;
+	const INPUT_WIDTH: u32 = 110;
+	const SWATCH_SIZE: u32 = 20;
+	const HISTORY_LENGTH: usize = 8;
+	/// Size of the cells the saturation / value square is drawn with
+	const CELL_SIZE: u32 = 4;
+	const CHECKER_SIZE: u32 = 8;
+
+	pub fn new(rect: Rect, color: Color) -> Self {
+		let mut color_picker = Self {
+			rect,
+			hue: 0,
+			saturation: 0.0,
+			value: 0.0,
+			alpha: 255,
+			hex_input: TextInput::new(Self::hex_input_rect(rect), None, None),
+			history: Vec::new(),
+			dragging: None,
+			hovered: false,
+			color_dragged: false,
+			drag: None,
+		};
+		color_picker.set_color(color);
+		color_picker
+	}
+
+	pub fn get_color(&self) -> Color {
+		let mut color = hsv_color(self.hue, self.saturation, self.value);
+		color.a = self.alpha;
+		color
+	}
+
+	pub fn set_color(&mut self, color: Color) {
+		self.set_hsva(color);
+		self.update_hex_input();
+	}
+
+	/// Sets the color without touching the hexadecimal input
+	fn set_hsva(&mut self, color: Color) {
+		let (hue, saturation, value) = color_to_hsv(color);
+		// The hue of greys is undefined, so it's kept
+		if saturation != 0.0 && value != 0.0 {
+			self.hue = hue;
+		}
+		self.saturation = saturation;
+		self.value = value;
+		self.alpha = color.a;
+	}
+
+	/// The last picked colors, from the most recent
+	pub fn get_history(&self) -> &Vec<Color> {
+		&self.history
+	}
+
+	/// Adds the current color at the beginning of the history
+	fn push_history(&mut self) {
+		let color = self.get_color();
+		self.history.retain(|old_color| *old_color != color);
+		self.history.insert(0, color);
+		self.history.truncate(Self::HISTORY_LENGTH);
+	}
+
+	fn update_hex_input(&mut self) {
+		self.hex_input.set_content(color_to_hex(self.get_color()));
+	}
+
+	/// Size of the saturation / value square, it's empty if the rect is too small for the other parts
+	fn square_size(rect: Rect) -> u32 {
+		let below_height = Self::BAR_THICKNESS + Self::INPUT_HEIGHT + Self::SWATCH_SIZE + 3 * Self::MARGIN as u32;
+		let width = rect.width().saturating_sub(3 * Self::MARGIN as u32 + Self::BAR_THICKNESS);
+		let height = rect.height().saturating_sub(2 * Self::MARGIN as u32 + below_height);
+		width.min(height)
+	}
+
+	fn square_rect(rect: Rect) -> Rect {
+		let size = Self::square_size(rect);
+		rect!(rect.left() + Self::MARGIN, rect.top() + Self::MARGIN, size, size)
+	}
+
+	fn hue_rect(rect: Rect) -> Rect {
+		let square = Self::square_rect(rect);
+		rect!(square.right() + Self::MARGIN, square.top(), Self::BAR_THICKNESS, square.height())
+	}
+
+	fn alpha_rect(rect: Rect) -> Rect {
+		let square = Self::square_rect(rect);
+		rect!(
+			square.left(),
+			square.bottom() + Self::MARGIN,
+			square.width() + Self::MARGIN as u32 + Self::BAR_THICKNESS,
+			Self::BAR_THICKNESS
+		)
+	}
+
+	fn hex_input_rect(rect: Rect) -> Rect {
+		let alpha = Self::alpha_rect(rect);
+		rect!(alpha.left(), alpha.bottom() + Self::MARGIN, Self::INPUT_WIDTH, Self::INPUT_HEIGHT)
+	}
+
+	fn preview_rect(rect: Rect) -> Rect {
+		let hex_input = Self::hex_input_rect(rect);
+		let alpha = Self::alpha_rect(rect);
+		rect!(
+			hex_input.right() + Self::MARGIN,
+			hex_input.top(),
+			(alpha.right() - hex_input.right() - Self::MARGIN).max(1),
+			Self::INPUT_HEIGHT
+		)
+	}
+
+	fn swatch_rect(rect: Rect, index: usize) -> Rect {
+		let hex_input = Self::hex_input_rect(rect);
+		rect!(
+			hex_input.left() + index as i32 * (Self::SWATCH_SIZE as i32 + Self::MARGIN / 2),
+			hex_input.bottom() + Self::MARGIN,
+			Self::SWATCH_SIZE,
+			Self::SWATCH_SIZE
+		)
+	}
+
+	/// Relative position (0.0 - 1.0) of a point in a rect
+	fn relative_position(rect: Rect, point: Point) -> (f32, f32) {
+		(
+			((point.x - rect.left()) as f32 / rect.width() as f32).clamp(0.0, 1.0),
+			((point.y - rect.top()) as f32 / rect.height() as f32).clamp(0.0, 1.0),
+		)
+	}
+
+	fn draw_checker(canvas: &mut Canvas<Window>, rect: Rect) {
+		fill_rect(canvas, rect, Colors::WHITE);
+		for x in (0..rect.width()).step_by(Self::CHECKER_SIZE as usize) {
+			for y in (0..rect.height()).step_by(Self::CHECKER_SIZE as usize) {
+				if (x / Self::CHECKER_SIZE + y / Self::CHECKER_SIZE) % 2 == 0 {
+					let width = Self::CHECKER_SIZE.min(rect.width() - x);
+					let height = Self::CHECKER_SIZE.min(rect.height() - y);
+					let cell = rect!(rect.left() + x as i32, rect.top() + y as i32, width, height);
+					fill_rect(canvas, cell, Colors::LIGHT_GREY);
+				}
+			}
+		}
+	}
+}
+
+impl Widget for ColorPicker {
+	fn update(&mut self, input: &Input, delta: f64, text_drawer: &mut TextDrawer) -> bool {
+		let mut changed = false;
+
+		let mouse_position = Point::new(input.mouse.position.x, input.mouse.position.y);
+		let hovered = self.rect.contains_point(mouse_position);
+		if hovered != self.hovered {
+			self.hovered = hovered;
+			changed = true;
+		}
+
+		// Hexadecimal input
+		let old_hex = self.hex_input.content.clone();
+		changed |= self.hex_input.update(input, delta, text_drawer);
+		if self.hex_input.content != old_hex {
+			if let Some(color) = hex_to_color(&self.hex_input.content) {
+				self.set_hsva(color);
+				changed = true;
+			}
+		}
+
+		// Mouse click
+		if input.mouse.left_button.is_pressed() {
+			self.dragging = if Self::square_rect(self.rect).contains_point(mouse_position) {
+				Some(Dragging::SaturationValue)
+			} else if Self::hue_rect(self.rect).contains_point(mouse_position) {
+				Some(Dragging::Hue)
+			} else if Self::alpha_rect(self.rect).contains_point(mouse_position) {
+				Some(Dragging::Alpha)
+			} else {
+				None
+			};
+			if let Some(index) = (0..self.history.len())
+				.find(|index| Self::swatch_rect(self.rect, *index).contains_point(mouse_position))
+			{
+				self.set_color(self.history[index]);
+				self.push_history();
+				changed = true;
+			}
+		}
+
+		// Color dragged out of the preview
+		let left_button = &input.mouse.left_button;
+		if left_button.is_up() {
+			self.color_dragged = false;
+		} else if left_button.is_dragging() && !self.color_dragged {
+			let origin = left_button.get_drag_origin().map(|origin| Point::new(origin.x, origin.y));
+			if origin.is_some_and(|origin| Self::preview_rect(self.rect).contains_point(origin)) {
+				self.color_dragged = true;
+				self.drag = Some(self.get_color());
+			}
+		}
+
+		// Mouse drag
+		if let Some(dragging) = self.dragging {
+			match dragging {
+				Dragging::SaturationValue => {
+					let (x, y) = Self::relative_position(Self::square_rect(self.rect), mouse_position);
+					(self.saturation, self.value) = (x, 1.0 - y);
+				}
+				Dragging::Hue => {
+					let (_, y) = Self::relative_position(Self::hue_rect(self.rect), mouse_position);
+					self.hue = (y * 360.0) as u16;
+				}
+				Dragging::Alpha => {
+					let (x, _) = Self::relative_position(Self::alpha_rect(self.rect), mouse_position);
+					self.alpha = (x * 255.0) as u8;
+				}
+			}
+			if !self.hex_input.is_focused() {
+				self.update_hex_input();
+			}
+			changed = true;
+
+			if input.mouse.left_button.is_released() {
+				self.dragging = None;
+				self.push_history();
+			}
+		} else if self.hex_input.is_focused() && input.keys_state.enter.is_pressed() {
+			self.push_history();
+			changed = true;
+		}
+
+		changed
+	}
+
+	fn draw(&self, canvas: &mut Canvas<Window>, text_drawer: &TextDrawer) {
+		fill_rect(canvas, self.rect, Colors::WHITE);
+		draw_rect(canvas, self.rect, Colors::BLACK);
+
+		// Saturation / value square
+		let square = Self::square_rect(self.rect);
+		let cells = square.width() / Self::CELL_SIZE;
+		for i in 0..cells {
+			for j in 0..cells {
+				let color = hsv_color(self.hue, i as f32 / cells as f32, 1.0 - j as f32 / cells as f32);
+				let cell = rect!(
+					square.left() + (i * Self::CELL_SIZE) as i32,
+					square.top() + (j * Self::CELL_SIZE) as i32,
+					Self::CELL_SIZE,
+					Self::CELL_SIZE
+				);
+				fill_rect(canvas, cell, color);
+			}
+		}
+		draw_rect(canvas, square, Colors::BLACK);
+		let x = square.left() + (self.saturation * square.width() as f32) as i32;
+		let y = square.top() + ((1.0 - self.value) * square.height() as f32) as i32;
+		let cursor_color = if self.value > 0.5 { Colors::BLACK } else { Colors::WHITE };
+		DrawRenderer::circle(canvas, x as i16, y as i16, 4, cursor_color).unwrap();
+
+		// Hue bar
+		let hue_bar = Self::hue_rect(self.rect);
+		for y in 0..hue_bar.height() {
+			let color = hsv_color((y * 360 / hue_bar.height()) as u16, 1.0, 1.0);
+			let y = (hue_bar.top() + y as i32) as i16;
+			DrawRenderer::hline(canvas, hue_bar.left() as i16, hue_bar.right() as i16 - 1, y, color).unwrap();
+		}
+		draw_rect(canvas, hue_bar, Colors::BLACK);
+		let y = hue_bar.top() + (self.hue as u32 * hue_bar.height() / 360) as i32;
+		draw_rect(canvas, rect!(hue_bar.left() - 2, y - 2, hue_bar.width() + 4, 5), Colors::BLACK);
+
+		// Alpha bar
+		let alpha_bar = Self::alpha_rect(self.rect);
+		Self::draw_checker(canvas, alpha_bar);
+		canvas.set_blend_mode(BlendMode::Blend);
+		let mut color = hsv_color(self.hue, self.saturation, self.value);
+		for x in 0..alpha_bar.width() {
+			color.a = (x * 255 / alpha_bar.width()) as u8;
+			fill_rect(canvas, rect!(alpha_bar.left() + x as i32, alpha_bar.top(), 1, alpha_bar.height()), color);
+		}
+		canvas.set_blend_mode(BlendMode::None);
+		draw_rect(canvas, alpha_bar, Colors::BLACK);
+		let x = alpha_bar.left() + (self.alpha as u32 * alpha_bar.width() / 255) as i32;
+		draw_rect(canvas, rect!(x - 2, alpha_bar.top() - 2, 5, alpha_bar.height() + 4), Colors::BLACK);
+
+		// Hexadecimal input and preview
+		self.hex_input.draw(canvas, text_drawer);
+		let preview = Self::preview_rect(self.rect);
+		Self::draw_checker(canvas, preview);
+		canvas.set_blend_mode(BlendMode::Blend);
+		fill_rect(canvas, preview, self.get_color());
+		canvas.set_blend_mode(BlendMode::None);
+		draw_rect(canvas, preview, Colors::BLACK);
+
+		// History
+		for (index, color) in self.history.iter().enumerate() {
+			let swatch = Self::swatch_rect(self.rect, index);
+			if swatch.right() > self.rect.right() {
+				break;
+			}
+			Self::draw_checker(canvas, swatch);
+			canvas.set_blend_mode(BlendMode::Blend);
+			fill_rect(canvas, swatch, *color);
+			canvas.set_blend_mode(BlendMode::None);
+			draw_rect(canvas, swatch, Colors::BLACK);
+		}
+	}
+
+	fn is_hovered(&self) -> bool {
+		self.hovered
+	}
+
+	/// The focus goes to the hexadecimal input
+	fn is_focusable(&self) -> bool {
+		true
+	}
+
+	fn is_focused(&self) -> bool {
+		self.hex_input.is_focused()
+	}
+
+	fn set_focused(&mut self, focused: bool) {
+		self.hex_input.set_focused(focused);
+	}
+
+	fn get_rect(&self) -> Option<Rect> {
+		Some(self.rect)
+	}
+
+	fn access_node(&self) -> Option<AccessNode> {
+		Some(AccessNode { value: Some(color_to_hex(self.get_color())), ..AccessNode::new(Role::ColorPicker, "") })
+	}
+
+	fn take_drag(&mut self) -> Option<(Box<dyn Any>, DragPreview)> {
+		self.drag.take().map(|color| (Box::new(color) as Box<dyn Any>, DragPreview::Color(color)))
+	}
+
+	fn accepts_drop(&self, payload: &dyn Any) -> bool {
+		payload.is::<Color>()
+	}
+
+	fn receive_drop(&mut self, payload: &dyn Any) -> bool {
+		match payload.downcast_ref::<Color>() {
+			Some(color) => {
+				self.set_color(*color);
+				self.push_history();
+				true
+			}
+			None => false,
+		}
+	}
+
+	fn get_cursor(&self) -> Option<MouseCursor> {
+		if self.dragging.is_some() {
+			Some(MouseCursor::Crosshair)
+		} else {
+			self.hex_input.get_cursor().or(self.hovered.then_some(MouseCursor::Crosshair))
+		}
+	}
+
+	fn reset_interaction(&mut self) {
+		self.hex_input.reset_interaction();
+		self.hovered = false;
+		self.dragging = None;
+		self.color_dragged = false;
+		self.drag = None;
+	}
+
+	fn get_widget_value(&self) -> Option<WidgetValue> {
+		Some(WidgetValue::Color(self.get_color()))
+	}
+}
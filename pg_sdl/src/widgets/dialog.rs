@@ -0,0 +1,340 @@
+use crate::canvas::{draw_rounded_rect, fill_rounded_rect};
+use crate::input::MouseCursor;
+use crate::prelude::*;
+use crate::widgets::{AccessNode, Role};
+use sdl2::ttf::FontStyle;
+
+/// What kind of dialog it is, and so which buttons (and text field) it has:
+///
+/// - **Alert**: a message with an **Ok** button
+/// - **Confirm**: a question with **Ok** and **Cancel** buttons
+/// - **Prompt**: a question with a text field, **Ok** and **Cancel** buttons
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum DialogKind {
+	Alert,
+	Confirm,
+	Prompt,
+}
+
+/// The answer given by the user when a dialog is closed
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum DialogResult {
+	Confirmed,
+	Cancelled,
+	/// The content of the text field of a prompt dialog
+	Submitted(String),
+}
+
+pub struct DialogStyle {
+	background_color: Color,
+	contour_color: Color,
+	focus_color: Color,
+	button_color: Color,
+	corner_radius: u16,
+	title_style: TextStyle,
+	message_style: TextStyle,
+}
+
+impl Default for DialogStyle {
+	fn default() -> Self {
+		Self {
+			background_color: Colors::WHITE,
+			contour_color: Colors::BLACK,
+			focus_color: paler(Colors::BLUE, 0.9),
+			button_color: Colors::LIGHT_GREY,
+			corner_radius: 8,
+			title_style: TextStyle::new(20, None, Color::BLACK, FontStyle::BOLD),
+			message_style: TextStyle::default(),
+		}
+	}
+}
+
+/// A dialog is a modal widget drawn above everything else.
+///
+/// While it is open, the widgets behind it don't receive any input.
+/// **Enter** triggers the focused button (**Ok** by default), **Escape** cancels
+/// and **Tab** / **Shift+Tab** cycle the focus between the dialog's elements.
+pub struct Dialog {
+	rect: Rect,
+	style: DialogStyle,
+	kind: DialogKind,
+	title: String,
+	message: String,
+	ok_button: Button,
+	cancel_button: Option<Button>,
+	text_input: Option<TextInput>,
+	/// Index of the focused element in the focus chain (text field, ok, cancel)
+	focus: usize,
+	open: bool,
+	result: Option<DialogResult>,
+}
+
+impl Dialog {
+	const MARGIN: i32 = 12;
+	const BUTTON_WIDTH: u32 = 90;
+	const BUTTON_HEIGHT: u32 = 30;
+
+	pub fn new(rect: Rect, style: Option<DialogStyle>, kind: DialogKind, title: String, message: String) -> Self {
+		let style = style.unwrap_or_default();
+		let button = |rect: Rect, text: &str| {
+			Button::new(style.button_color, rect, Some(4), TextStyle::default(), text.to_string())
+		};
+		// The ok button is on the left of the cancel button, if there is one
+		let has_cancel_button = kind != DialogKind::Alert;
+		let ok_button = button(Self::button_rect(rect, has_cancel_button as i32), "Ok");
+		let cancel_button = if has_cancel_button { Some(button(Self::button_rect(rect, 0), "Cancel")) } else { None };
+		let text_input = match kind {
+			DialogKind::Prompt => Some(TextInput::new(
+				rect!(
+					rect.left() + Self::MARGIN,
+					rect.bottom() - 2 * (Self::MARGIN + Self::BUTTON_HEIGHT as i32),
+					rect.width() - 2 * Self::MARGIN as u32,
+					Self::BUTTON_HEIGHT
+				),
+				None,
+				None,
+			)),
+			_ => None,
+		};
+		Self {
+			rect,
+			style,
+			kind,
+			title,
+			message,
+			ok_button,
+			cancel_button,
+			text_input,
+			focus: 0,
+			open: false,
+			result: None,
+		}
+	}
+
+	/// A dialog showing a message with an **Ok** button
+	pub fn alert(rect: Rect, title: &str, message: &str) -> Self {
+		Self::new(rect, None, DialogKind::Alert, title.to_string(), message.to_string())
+	}
+
+	/// A dialog asking a question with **Ok** and **Cancel** buttons
+	pub fn confirm(rect: Rect, title: &str, message: &str) -> Self {
+		Self::new(rect, None, DialogKind::Confirm, title.to_string(), message.to_string())
+	}
+
+	/// A dialog asking for a text with **Ok** and **Cancel** buttons
+	pub fn prompt(rect: Rect, title: &str, message: &str, default_text: Option<String>) -> Self {
+		let mut dialog = Self::new(rect, None, DialogKind::Prompt, title.to_string(), message.to_string());
+		if let (Some(text_input), Some(default_text)) = (&mut dialog.text_input, default_text) {
+			text_input.set_content(default_text);
+		}
+		dialog
+	}
+
+	/// Rect of the n-th button, counted from the bottom right corner
+	fn button_rect(rect: Rect, index: i32) -> Rect {
+		rect!(
+			rect.right() - (index + 1) * (Self::MARGIN + Self::BUTTON_WIDTH as i32),
+			rect.bottom() - Self::MARGIN - Self::BUTTON_HEIGHT as i32,
+			Self::BUTTON_WIDTH,
+			Self::BUTTON_HEIGHT
+		)
+	}
+
+	/// Opens the dialog (and clears the previous result)
+	pub fn open(&mut self) {
+		self.open = true;
+		self.result = None;
+		self.focus = 0;
+		self.update_text_input_focus();
+	}
+
+	pub fn is_open(&self) -> bool {
+		self.open
+	}
+
+	pub fn kind(&self) -> DialogKind {
+		self.kind
+	}
+
+	pub fn set_message(&mut self, message: String) {
+		self.message = message;
+	}
+
+	/// Returns the result of the dialog once it's been closed, only once
+	pub fn take_result(&mut self) -> Option<DialogResult> {
+		self.result.take()
+	}
+
+	fn close(&mut self, result: DialogResult) {
+		self.open = false;
+		self.result = Some(result);
+		if let Some(text_input) = &mut self.text_input {
+			text_input.set_focused(false);
+		}
+	}
+
+	fn confirm_result(&self) -> DialogResult {
+		match &self.text_input {
+			Some(text_input) => DialogResult::Submitted(text_input.content.clone()),
+			None => DialogResult::Confirmed,
+		}
+	}
+
+	fn focus_chain_length(&self) -> usize {
+		1 + self.cancel_button.is_some() as usize + self.text_input.is_some() as usize
+	}
+
+	/// Rect of the focused button, or None if the text field is focused
+	fn focused_button_rect(&self) -> Option<Rect> {
+		if self.text_input.is_some() && self.focus == 0 {
+			None
+		} else if self.is_cancel_focused() {
+			Some(Self::button_rect(self.rect, 0))
+		} else {
+			Some(Self::button_rect(self.rect, self.cancel_button.is_some() as i32))
+		}
+	}
+
+	fn is_cancel_focused(&self) -> bool {
+		self.cancel_button.is_some() && self.focus == self.focus_chain_length() - 1
+	}
+
+	fn update_text_input_focus(&mut self) {
+		let focused = self.focus == 0;
+		if let Some(text_input) = &mut self.text_input {
+			text_input.set_focused(focused);
+		}
+	}
+}
+
+impl Widget for Dialog {
+	fn update(&mut self, input: &Input, delta: f64, text_drawer: &mut TextDrawer) -> bool {
+		if !self.open {
+			return false;
+		}
+		let mut changed = false;
+
+		changed |= self.ok_button.update(input, delta, text_drawer);
+		if let Some(cancel_button) = &mut self.cancel_button {
+			changed |= cancel_button.update(input, delta, text_drawer);
+		}
+		if let Some(text_input) = &mut self.text_input {
+			changed |= text_input.update(input, delta, text_drawer);
+			if text_input.is_focused() {
+				self.focus = 0;
+			}
+		}
+
+		// Focus trapping
+		if input.keys_state.tab.is_pressed() {
+			let length = self.focus_chain_length();
+			self.focus = if input.keys_state.lshift.is_down() || input.keys_state.rshift.is_down() {
+				(self.focus + length - 1) % length
+			} else {
+				(self.focus + 1) % length
+			};
+			self.update_text_input_focus();
+			changed = true;
+		}
+
+		if self.ok_button.state.is_pressed() {
+			self.close(self.confirm_result());
+		} else if self.cancel_button.as_ref().is_some_and(|button| button.state.is_pressed())
+			|| input.keys_state.escape.is_pressed()
+		{
+			self.close(DialogResult::Cancelled);
+		} else if input.keys_state.enter.is_pressed() {
+			if self.is_cancel_focused() {
+				self.close(DialogResult::Cancelled);
+			} else {
+				self.close(self.confirm_result());
+			}
+		}
+
+		changed | !self.open
+	}
+
+	fn draw(&self, canvas: &mut Canvas<Window>, text_drawer: &TextDrawer) {
+		if !self.open {
+			return;
+		}
+		fill_rounded_rect(canvas, self.rect, self.style.background_color, self.style.corner_radius);
+		draw_rounded_rect(canvas, self.rect, self.style.contour_color, self.style.corner_radius);
+
+		// Title and message
+		let mut position = point!(self.rect.left() + Self::MARGIN, self.rect.top() + Self::MARGIN);
+		if !self.title.is_empty() {
+			text_drawer.draw(canvas, position, &self.style.title_style, &self.title, Align::TopLeft);
+			position.y += text_drawer.text_size(&self.style.title_style, &self.title).1 as i32 + Self::MARGIN;
+		}
+		for line in self.message.lines() {
+			if !line.is_empty() {
+				text_drawer.draw(canvas, position, &self.style.message_style, line, Align::TopLeft);
+			}
+			position.y += self.style.message_style.font_size as i32 + 4;
+		}
+
+		// Elements
+		if let Some(text_input) = &self.text_input {
+			text_input.draw(canvas, text_drawer);
+		}
+		self.ok_button.draw(canvas, text_drawer);
+		if let Some(cancel_button) = &self.cancel_button {
+			cancel_button.draw(canvas, text_drawer);
+		}
+
+		// Focus ring
+		if let Some(rect) = self.focused_button_rect() {
+			let rect = rect!(rect.left() - 2, rect.top() - 2, rect.width() + 4, rect.height() + 4);
+			draw_rounded_rect(canvas, rect, self.style.focus_color, 6);
+		}
+	}
+
+	fn is_modal(&self) -> bool {
+		self.open
+	}
+
+	fn get_rect(&self) -> Option<Rect> {
+		Some(self.rect)
+	}
+
+	/// The message, the text field and the buttons, in the focus order
+	fn access_node(&self) -> Option<AccessNode> {
+		if !self.open {
+			return None;
+		}
+		let mut node = AccessNode::new(Role::Dialog, &self.title);
+		node.children.push(AccessNode { id: "message".to_string(), ..AccessNode::new(Role::Label, &self.message) });
+		let text_input_focused = self.text_input.as_ref().is_some_and(|text_input| text_input.is_focused());
+		let children = [
+			("text", self.text_input.as_ref().map(|text_input| text_input as &dyn Widget), text_input_focused),
+			("ok", Some(&self.ok_button as &dyn Widget), !text_input_focused && !self.is_cancel_focused()),
+			("cancel", self.cancel_button.as_ref().map(|button| button as &dyn Widget), self.is_cancel_focused()),
+		];
+		for (id, widget, focused) in children {
+			if let Some(mut child) = widget.and_then(|widget| widget.access_node()) {
+				child.id = id.to_string();
+				child.focused = focused;
+				child.bounds = widget.and_then(|widget| widget.get_rect());
+				node.children.push(child);
+			}
+		}
+		Some(node)
+	}
+
+	fn get_cursor(&self) -> Option<MouseCursor> {
+		let text_input_cursor = self.text_input.as_ref().and_then(|text_input| text_input.get_cursor());
+		let cancel_cursor = self.cancel_button.as_ref().and_then(|button| button.get_cursor());
+		text_input_cursor.or(self.ok_button.get_cursor()).or(cancel_cursor)
+	}
+
+	fn reset_interaction(&mut self) {
+		self.ok_button.reset_interaction();
+		if let Some(cancel_button) = &mut self.cancel_button {
+			cancel_button.reset_interaction();
+		}
+		if let Some(text_input) = &mut self.text_input {
+			text_input.reset_interaction();
+		}
+	}
+}
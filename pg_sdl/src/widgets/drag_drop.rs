@@ -0,0 +1,179 @@
+use crate::canvas::{draw_rounded_rect, fill_rounded_rect};
+use crate::color::Colors;
+use crate::input::Input;
+use crate::style::Align;
+use crate::text::{TextDrawer, TextStyle};
+use sdl2::pixels::Color;
+use sdl2::rect::{Point, Rect};
+use sdl2::render::Canvas;
+use sdl2::video::Window;
+use std::any::Any;
+
+/// Function drawing a custom drag preview at the mouse position
+pub type PreviewDrawer = Box<dyn Fn(&mut Canvas<Window>, &TextDrawer, Point)>;
+
+/// What is drawn under the mouse while something is dragged
+pub enum DragPreview {
+	None,
+	Text(String),
+	Color(Color),
+	Custom(PreviewDrawer),
+}
+
+struct Drag {
+	source: String,
+	payload: Box<dyn Any>,
+	preview: DragPreview,
+	/// Target hovered this frame, and whether it accepts the payload
+	target: Option<(String, bool)>,
+}
+
+struct Dropped {
+	source: String,
+	target: String,
+	payload: Box<dyn Any>,
+}
+
+/// Drag and drop between the widgets and the app.
+///
+/// A source starts a drag with a payload of any type. While it's dragged, the targets under the mouse tell whether
+/// they accept it (the widgets through `Widget::accepts_drop`, the app with `hover`), which colors the preview.
+/// When the left button is released over a target that accepts it, the payload is dropped for one frame:
+/// the widgets get it through `Widget::receive_drop`, the app with `take_drop`.
+///
+/// The files and texts dropped on the window from the OS are in `Input::dropped_files` and `Input::dropped_texts`
+pub struct DragDrop {
+	drag: Option<Drag>,
+	dropped: Option<Dropped>,
+}
+
+impl Default for DragDrop {
+	fn default() -> Self {
+		Self::new()
+	}
+}
+
+impl DragDrop {
+	const PREVIEW_OFFSET: i32 = 12;
+	const PREVIEW_MARGIN: u32 = 6;
+	const SWATCH_SIZE: u32 = 24;
+	const ACCEPTED_COLOR: Color = Colors::GREEN;
+	const REJECTED_COLOR: Color = Colors::RED;
+
+	pub fn new() -> Self {
+		DragDrop { drag: None, dropped: None }
+	}
+
+	/// Starts dragging 'payload' from 'source' (it replaces the current drag, if any)
+	pub fn start<T: Any>(&mut self, source: &str, payload: T, preview: DragPreview) {
+		self.start_boxed(source, Box::new(payload), preview);
+	}
+
+	pub(crate) fn start_boxed(&mut self, source: &str, payload: Box<dyn Any>, preview: DragPreview) {
+		self.drag = Some(Drag { source: source.to_string(), payload, preview, target: None });
+	}
+
+	pub fn cancel(&mut self) {
+		self.drag = None;
+	}
+
+	pub fn is_dragging(&self) -> bool {
+		self.drag.is_some()
+	}
+
+	pub fn get_source(&self) -> Option<&str> {
+		self.drag.as_ref().map(|drag| drag.source.as_str())
+	}
+
+	/// The dragged payload, if there is one of type T
+	pub fn get_payload<T: Any>(&self) -> Option<&T> {
+		self.drag.as_ref().and_then(|drag| drag.payload.downcast_ref::<T>())
+	}
+
+	pub(crate) fn get_any_payload(&self) -> Option<&dyn Any> {
+		self.drag.as_ref().map(|drag| drag.payload.as_ref())
+	}
+
+	/// Tells that the drag is over 'target' and whether it accepts the payload,
+	/// it has to be called every frame while the mouse is over the target
+	pub fn hover(&mut self, target: &str, accepted: bool) {
+		if let Some(drag) = &mut self.drag {
+			drag.target = Some((target.to_string(), accepted));
+		}
+	}
+
+	/// The target hovered by the drag this frame, and whether it accepts the payload
+	pub fn get_hovered_target(&self) -> Option<(&str, bool)> {
+		self.drag.as_ref()?.target.as_ref().map(|(target, accepted)| (target.as_str(), *accepted))
+	}
+
+	/// Source and target of the payload dropped this frame
+	pub fn get_drop(&self) -> Option<(&str, &str)> {
+		self.dropped.as_ref().map(|dropped| (dropped.source.as_str(), dropped.target.as_str()))
+	}
+
+	/// The payload dropped on 'target' this frame, if it's of type T
+	pub fn take_drop<T: Any>(&mut self, target: &str) -> Option<T> {
+		let dropped = self.dropped.take_if(|dropped| dropped.target == target && dropped.payload.is::<T>())?;
+		dropped.payload.downcast::<T>().ok().map(|payload| *payload)
+	}
+
+	pub(crate) fn get_dropped(&self) -> Option<(&str, &dyn Any)> {
+		self.dropped.as_ref().map(|dropped| (dropped.target.as_str(), dropped.payload.as_ref()))
+	}
+
+	/// Drops the payload when the left button is released (or cancels the drag with Escape),
+	/// returns true if the drag ended.
+	///
+	/// Otherwise the hovered target is cleared, to be set again by the targets during the frame
+	pub(crate) fn update(&mut self, input: &Input) -> bool {
+		self.dropped = None;
+		let Some(drag) = &mut self.drag else {
+			return false;
+		};
+		if input.keys_state.escape.is_pressed() {
+			self.drag = None;
+			return true;
+		}
+		if !input.mouse.left_button.is_down() {
+			let drag = self.drag.take().unwrap();
+			if let Some((target, true)) = drag.target {
+				self.dropped = Some(Dropped { source: drag.source, target, payload: drag.payload });
+			}
+			return true;
+		}
+		drag.target = None;
+		false
+	}
+
+	pub(crate) fn draw(&self, canvas: &mut Canvas<Window>, text_drawer: &TextDrawer, mouse_position: Point) {
+		let Some(drag) = &self.drag else {
+			return;
+		};
+		let position = mouse_position.offset(Self::PREVIEW_OFFSET, Self::PREVIEW_OFFSET);
+		let rect = match &drag.preview {
+			DragPreview::None => return,
+			DragPreview::Custom(draw) => return draw(canvas, text_drawer, mouse_position),
+			DragPreview::Text(text) => {
+				let text_style = TextStyle::default();
+				let (width, height) = text_drawer.text_size(&text_style, text);
+				let margin = Self::PREVIEW_MARGIN;
+				let rect = Rect::new(position.x, position.y, width + 2 * margin, height + 2 * margin);
+				fill_rounded_rect(canvas, rect, Colors::WHITE, 4);
+				text_drawer.draw(canvas, rect.center(), &text_style, text, Align::Center);
+				rect
+			}
+			DragPreview::Color(color) => {
+				let rect = Rect::new(position.x, position.y, Self::SWATCH_SIZE, Self::SWATCH_SIZE);
+				fill_rounded_rect(canvas, rect, *color, 4);
+				rect
+			}
+		};
+		let contour_color = match drag.target {
+			Some((_, true)) => Self::ACCEPTED_COLOR,
+			Some((_, false)) => Self::REJECTED_COLOR,
+			None => Colors::BLACK,
+		};
+		draw_rounded_rect(canvas, rect, contour_color, 4);
+	}
+}
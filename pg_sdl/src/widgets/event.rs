@@ -0,0 +1,49 @@
+use crate::widgets::Widgets;
+use sdl2::pixels::Color;
+
+/// Value of a widget, carried by the `Changed` events
+#[derive(Clone, PartialEq, Debug)]
+pub enum WidgetValue {
+	Number(f64),
+	Text(String),
+	Color(Color),
+}
+
+impl WidgetValue {
+	pub fn as_number(&self) -> Option<f64> {
+		match self {
+			WidgetValue::Number(number) => Some(*number),
+			_ => None,
+		}
+	}
+
+	pub fn as_text(&self) -> Option<&str> {
+		match self {
+			WidgetValue::Text(text) => Some(text),
+			_ => None,
+		}
+	}
+
+	pub fn as_color(&self) -> Option<Color> {
+		match self {
+			WidgetValue::Color(color) => Some(*color),
+			_ => None,
+		}
+	}
+}
+
+/// Something the user did to a widget during the update
+#[derive(Clone, PartialEq, Debug)]
+pub enum WidgetEvent {
+	/// A button was pressed
+	Clicked,
+	/// The value of a slider, a number input, a text field or a color picker was changed by the user
+	Changed(WidgetValue),
+	/// A switch was toggled, to its new state
+	Toggled(bool),
+	/// Enter was pressed in a text input, with its content
+	Submitted(String),
+}
+
+/// Function called with the events of a widget, it can change the other widgets
+pub type EventCallback = Box<dyn FnMut(&WidgetEvent, &mut Widgets)>;
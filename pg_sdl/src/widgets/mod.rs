@@ -1,67 +1,774 @@
+pub mod access;
+pub mod binding;
 pub mod button;
+pub mod color_picker;
+pub mod dialog;
+pub mod drag_drop;
+pub mod event;
+pub mod number_input;
+pub mod progress_bar;
 pub mod slider;
+pub mod spinner;
 pub mod switch;
+pub mod text_area;
 pub mod text_input;
+pub mod tooltip;
 
-use crate::input::Input;
+use crate::canvas::{draw_rounded_rect, fill_rounded_rect};
+use crate::color::Colors;
+use crate::input::{Input, KeyState, MouseCursor};
 use crate::text::TextDrawer;
 use as_any::{AsAny, Downcast};
-use sdl2::render::Canvas;
+use nalgebra::Vector2;
+use sdl2::pixels::Color;
+use sdl2::rect::{Point, Rect};
+use sdl2::render::{BlendMode, Canvas};
 use sdl2::video::Window;
-use std::collections::HashMap;
+use std::any::Any;
+use std::collections::{HashMap, HashSet};
 
+pub use access::{AccessBackend, AccessNode, Role};
+pub use binding::Binding;
 pub use button::Button;
+pub use color_picker::ColorPicker;
+pub use dialog::{Dialog, DialogKind, DialogResult, DialogStyle};
+pub use drag_drop::{DragDrop, DragPreview, PreviewDrawer};
+pub use event::{EventCallback, WidgetEvent, WidgetValue};
+pub use number_input::{NumberInput, NumberType};
+pub use progress_bar::{Progress, ProgressBar, ProgressBarStyle};
 pub use slider::Slider;
 pub use slider::SliderType;
-pub use text_input::{TextInput, TextInputStyle};
+pub use spinner::{Spinner, SpinnerStyle};
+pub use text_area::{Highlighter, TextArea, TextAreaStyle};
+pub use text_input::{TextInput, TextInputStyle, Validator};
+pub use tooltip::{Tooltip, TooltipContent, TooltipDrawer, TooltipStyle};
 
 const HOVER: f32 = 0.94;
 const PUSH: f32 = 0.80;
+/// Color of the backdrop drawn behind modal widgets
+const BACKDROP: Color = Color::RGBA(0, 0, 0, 120);
 
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
 pub enum Orientation {
 	Horizontal,
 	Vertical,
 }
 
+impl Orientation {
+	/// Horizontal if the rect is wider than it is high, vertical otherwise
+	pub fn from_rect(rect: Rect) -> Self {
+		if rect.width() > rect.height() {
+			Orientation::Horizontal
+		} else {
+			Orientation::Vertical
+		}
+	}
+}
+
+/// Look of the ring drawn around the focused widget
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct FocusStyle {
+	pub color: Color,
+	/// Width of the ring (in pixels)
+	pub thickness: u32,
+	/// Space between the widget and the ring (in pixels)
+	pub margin: i32,
+	pub corner_radius: u16,
+}
+
+impl Default for FocusStyle {
+	fn default() -> Self {
+		Self { color: Colors::ROYAL_BLUE, thickness: 2, margin: 3, corner_radius: 6 }
+	}
+}
+
+/// Look of the disabled widgets, they are covered by a translucent overlay
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct DisabledStyle {
+	pub overlay_color: Color,
+	pub corner_radius: u16,
+}
+
+impl Default for DisabledStyle {
+	fn default() -> Self {
+		Self { overlay_color: Color::RGBA(255, 255, 255, 150), corner_radius: 4 }
+	}
+}
+
+/// State of the keys activating the focused widget: Enter, Space and the "ui activate" action (gamepad A button)
+fn activation_state(input: &Input) -> KeyState {
+	KeyState::most_advanced([input.keys_state.enter, input.keys_state.space, input.action("ui activate")].into_iter())
+}
+
 /// A widget is a UI object that can be interacted with to take inputs from the user.
 pub trait Widget: AsAny {
 	/// Update the widget based on the inputs
 	fn update(&mut self, input: &Input, delta: f64, text_drawer: &mut TextDrawer) -> bool;
 	/// Draw the widget on the canvas
 	fn draw(&self, canvas: &mut Canvas<Window>, text_drawer: &TextDrawer);
+	/// Whether the widget is currently modal: drawn above a dimmed backdrop, and the only one receiving inputs
+	fn is_modal(&self) -> bool {
+		false
+	}
+	/// Whether the mouse is over the widget
+	fn is_hovered(&self) -> bool {
+		false
+	}
+	/// Whether the widget can take the keyboard focus (with Tab or the D-pad)
+	fn is_focusable(&self) -> bool {
+		false
+	}
+	/// Whether the widget has the keyboard focus
+	fn is_focused(&self) -> bool {
+		false
+	}
+	/// Gives or takes the keyboard focus, `Widgets` keeps a single focused widget
+	fn set_focused(&mut self, _focused: bool) {}
+	/// Whether the widget uses Tab itself when it's focused (Ctrl+Tab still moves the focus)
+	fn captures_tab(&self) -> bool {
+		false
+	}
+	/// Area of the widget, to draw the focus ring and to navigate with the D-pad
+	fn get_rect(&self) -> Option<Rect> {
+		None
+	}
+	/// Describes the widget for the accessibility tree, or None if it's hidden (like a closed dialog).
+	///
+	/// The id, the empty label, the focus and the bounds are filled by `Widgets`
+	fn access_node(&self) -> Option<AccessNode> {
+		Some(AccessNode::new(Role::Unknown, ""))
+	}
+	/// Cursor wanted by the widget, usually while it's hovered or dragged
+	fn get_cursor(&self) -> Option<MouseCursor> {
+		None
+	}
+	/// A drag started from the widget during the update, with its payload and its preview
+	/// (`Widgets` takes it after each update)
+	fn take_drag(&mut self) -> Option<(Box<dyn Any>, DragPreview)> {
+		None
+	}
+	/// Whether the widget accepts the payload dragged over it
+	fn accepts_drop(&self, _payload: &dyn Any) -> bool {
+		false
+	}
+	/// Receives a payload it accepts dropped on it, returns true if it changed
+	fn receive_drop(&mut self, _payload: &dyn Any) -> bool {
+		false
+	}
+	/// Forgets the hover, the presses and the drags when the widget stops being updated (disabled or hidden)
+	fn reset_interaction(&mut self) {}
+	/// Value of the widget, a `Changed` event is sent when the user changes it
+	fn get_widget_value(&self) -> Option<WidgetValue> {
+		None
+	}
+	/// Counter incremented when the value changes, for the widgets whose value is costly to get (like a text area):
+	/// `Widgets` then only gets the value when it changed
+	fn get_value_revision(&self) -> Option<u64> {
+		None
+	}
+	/// Events of the last update other than the value changes (like a click)
+	fn get_events(&self) -> Vec<WidgetEvent> {
+		Vec::new()
+	}
+	/// Takes the value the app set to the binding of the widget (`Binding::set`), returns true if it changed
+	fn pull_binding(&mut self) -> bool {
+		false
+	}
+	/// Writes the value of the widget to its binding, after the update and the drops / focus changes of the frame
+	fn push_binding(&mut self) {}
 }
 
-pub struct Widgets(HashMap<String, Box<dyn Widget>>);
+/// Value of a widget before the user interacts with it, to find out if it changed
+enum ValueSnapshot {
+	Revision(u64),
+	Value(Option<WidgetValue>),
+}
+
+impl ValueSnapshot {
+	fn new(widget: &dyn Widget) -> Self {
+		match widget.get_value_revision() {
+			Some(revision) => ValueSnapshot::Revision(revision),
+			None => ValueSnapshot::Value(widget.get_widget_value()),
+		}
+	}
+
+	/// The new value of the widget, if it changed since the snapshot
+	fn changed_value(self, widget: &dyn Widget) -> Option<WidgetValue> {
+		match self {
+			ValueSnapshot::Revision(revision) if widget.get_value_revision() == Some(revision) => None,
+			ValueSnapshot::Revision(_) => widget.get_widget_value(),
+			ValueSnapshot::Value(old_value) => {
+				widget.get_widget_value().filter(|value| Some(value) != old_value.as_ref())
+			}
+		}
+	}
+}
+
+/// Updates a widget and records its events, returns true if it has to be redrawn
+fn update_widget(
+	name: &str, widget: &mut Box<dyn Widget>, input: &Input, delta: f64, text_drawer: &mut TextDrawer,
+	events: &mut Vec<(String, WidgetEvent)>,
+) -> bool {
+	let snapshot = ValueSnapshot::new(widget.as_ref());
+	let redraw = widget.update(input, delta, text_drawer);
+	events.extend(widget.get_events().into_iter().map(|event| (name.to_string(), event)));
+	if let Some(value) = snapshot.changed_value(widget.as_ref()) {
+		events.push((name.to_string(), WidgetEvent::Changed(value)));
+	}
+	redraw
+}
+
+/// Calls 'f' on the widget outside of its update (like a drop or a focus change), and records the change of its value
+/// (it's written to its binding too)
+fn record_change<R>(
+	name: &str, widget: &mut Box<dyn Widget>, events: &mut Vec<(String, WidgetEvent)>,
+	f: impl FnOnce(&mut Box<dyn Widget>) -> R,
+) -> R {
+	let snapshot = ValueSnapshot::new(widget.as_ref());
+	let result = f(widget);
+	if let Some(value) = snapshot.changed_value(widget.as_ref()) {
+		widget.push_binding();
+		events.push((name.to_string(), WidgetEvent::Changed(value)));
+	}
+	result
+}
+
+pub struct Widgets {
+	widgets: HashMap<String, Box<dyn Widget>>,
+	tooltips: HashMap<String, Tooltip>,
+	/// Name of the hovered widget that has a tooltip, and for how long it's been hovered
+	tooltip_hover: Option<(String, f64)>,
+	mouse_position: Point,
+	/// Names of the widgets in the order they were added, it's the Tab traversal order
+	order: Vec<String>,
+	/// Name of the widget with the keyboard focus
+	focused: Option<String>,
+	focus_style: FocusStyle,
+	access_backend: Option<Box<dyn AccessBackend>>,
+	/// Last tree sent to the backend, to send it again only when it changes
+	access_tree: Option<AccessNode>,
+	/// Cursor set by the app for this frame, when no widget wants one
+	cursor: Option<MouseCursor>,
+	drag_drop: DragDrop,
+	/// Names of the disabled widgets, they are drawn greyed out and ignore the inputs
+	disabled: HashSet<String>,
+	/// Names of the hidden widgets, they are neither updated nor drawn
+	hidden: HashSet<String>,
+	disabled_style: DisabledStyle,
+	/// Events of the widgets during the last update, in order
+	events: Vec<(String, WidgetEvent)>,
+	callbacks: HashMap<String, Vec<EventCallback>>,
+}
 
 impl Widgets {
 	pub fn new() -> Self {
-		Widgets(HashMap::new())
+		Widgets {
+			widgets: HashMap::new(),
+			tooltips: HashMap::new(),
+			tooltip_hover: None,
+			mouse_position: Point::new(0, 0),
+			order: Vec::new(),
+			focused: None,
+			focus_style: FocusStyle::default(),
+			access_backend: None,
+			access_tree: None,
+			cursor: None,
+			drag_drop: DragDrop::new(),
+			disabled: HashSet::new(),
+			hidden: HashSet::new(),
+			disabled_style: DisabledStyle::default(),
+			events: Vec::new(),
+			callbacks: HashMap::new(),
+		}
 	}
 
 	pub fn add(&mut self, name: &str, widget: Box<dyn Widget>) {
-		self.0.insert(name.to_string(), widget);
+		if self.widgets.insert(name.to_string(), widget).is_none() {
+			self.order.push(name.to_string());
+		}
+	}
+
+	/// Adds a widget that shows a tooltip when it's hovered
+	pub fn add_with_tooltip(&mut self, name: &str, widget: Box<dyn Widget>, tooltip: Tooltip) {
+		self.add(name, widget);
+		self.set_tooltip(name, Some(tooltip));
+	}
+
+	/// Sets (or removes) the tooltip of a widget
+	pub fn set_tooltip(&mut self, name: &str, tooltip: Option<Tooltip>) {
+		match tooltip {
+			Some(tooltip) => self.tooltips.insert(name.to_string(), tooltip),
+			None => self.tooltips.remove(name),
+		};
+	}
+
+	/// Enables or disables a widget, a disabled widget is greyed out and ignores the inputs
+	pub fn set_enabled(&mut self, name: &str, enabled: bool) {
+		if enabled {
+			self.disabled.remove(name);
+		} else if self.widgets.contains_key(name) && self.disabled.insert(name.to_string()) {
+			self.deactivate(name);
+		}
+	}
+
+	pub fn is_enabled(&self, name: &str) -> bool {
+		!self.disabled.contains(name)
+	}
+
+	/// Shows or hides a widget, a hidden widget is neither updated nor drawn
+	pub fn set_visible(&mut self, name: &str, visible: bool) {
+		if visible {
+			self.hidden.remove(name);
+		} else if self.widgets.contains_key(name) && self.hidden.insert(name.to_string()) {
+			self.deactivate(name);
+		}
+	}
+
+	pub fn is_visible(&self, name: &str) -> bool {
+		!self.hidden.contains(name)
+	}
+
+	pub fn set_disabled_style(&mut self, disabled_style: DisabledStyle) {
+		self.disabled_style = disabled_style;
+	}
+
+	/// Whether the widget is enabled and visible, so that it's updated
+	fn is_active(&self, name: &str) -> bool {
+		self.is_enabled(name) && self.is_visible(name)
+	}
+
+	/// Takes the focus and the drag from a widget that is not updated anymore
+	fn deactivate(&mut self, name: &str) {
+		if self.focused.as_deref() == Some(name) {
+			self.focus(None);
+		}
+		if self.drag_drop.get_source() == Some(name) {
+			self.drag_drop.cancel();
+		}
+		self.widgets.get_mut(name).unwrap().reset_interaction();
 	}
 
 	pub fn get<T: Widget>(&self, name: &str) -> Option<&T> {
-		self.0.get(name).and_then(|w| w.as_ref().downcast_ref::<T>())
+		self.widgets.get(name).and_then(|w| w.as_ref().downcast_ref::<T>())
 	}
 
 	pub fn get_mut<T: Widget>(&mut self, name: &str) -> Option<&mut T> {
-		self.0.get_mut(name).and_then(|w| w.as_mut().downcast_mut::<T>())
+		self.widgets.get_mut(name).and_then(|w| w.as_mut().downcast_mut::<T>())
+	}
+
+	/// Sets the Tab traversal order, the widgets that are not in 'order' come after, in the order they were added
+	pub fn set_focus_order(&mut self, order: &[&str]) {
+		let mut new_order = order
+			.iter()
+			.filter(|name| self.widgets.contains_key(**name))
+			.map(|name| name.to_string())
+			.collect::<Vec<String>>();
+		new_order.extend(self.order.iter().filter(|name| !order.contains(&name.as_str())).cloned());
+		self.order = new_order;
+	}
+
+	pub fn set_focus_style(&mut self, focus_style: FocusStyle) {
+		self.focus_style = focus_style;
+	}
+
+	/// Name of the widget with the keyboard focus
+	pub fn get_focused(&self) -> Option<&str> {
+		self.focused.as_deref()
+	}
+
+	/// Gives the keyboard focus to a widget (or takes it from the focused widget if 'name' is None).
+	///
+	/// The value committed by the widget losing the focus (like a number input) is sent as a `Changed` event
+	pub fn focus(&mut self, name: Option<&str>) {
+		if let Some(old_name) = self.focused.take() {
+			if let Some(widget) = self.widgets.get_mut(&old_name) {
+				record_change(&old_name, widget, &mut self.events, |widget| widget.set_focused(false));
+			}
+		}
+		let name = name.filter(|name| self.is_active(name));
+		if let Some(widget) = name.and_then(|name| self.widgets.get_mut(name)) {
+			widget.set_focused(true);
+			self.focused = name.map(|name| name.to_string());
+		}
+	}
+
+	/// Keeps a single focused widget when the widgets focus themselves (like a clicked text input)
+	fn update_focus(&mut self, input: &Input) -> bool {
+		let newly_focused = self
+			.order
+			.iter()
+			.find(|name| Some(*name) != self.focused.as_ref() && self.widgets[*name].is_focused())
+			.cloned();
+		if let Some(name) = newly_focused {
+			for (other_name, widget) in self.widgets.iter_mut() {
+				if *other_name != name && widget.is_focused() {
+					record_change(other_name, widget, &mut self.events, |widget| widget.set_focused(false));
+				}
+			}
+			self.focused = Some(name);
+			return true;
+		}
+
+		if let Some(name) = self.focused.clone() {
+			let widget = self.widgets.get_mut(&name).unwrap();
+			// A click elsewhere takes the focus from the widgets that don't handle it themselves
+			if input.mouse.left_button.is_pressed() && !widget.is_hovered() {
+				record_change(&name, widget, &mut self.events, |widget| widget.set_focused(false));
+			}
+			if !widget.is_focused() {
+				self.focused = None;
+				return true;
+			}
+		}
+		false
+	}
+
+	/// Moves the focus with Tab / Shift+Tab, and with the D-pad towards the closest widget in its direction
+	fn update_focus_navigation(&mut self, input: &Input) -> bool {
+		let keys_state = &input.keys_state;
+		let ctrl = keys_state.lctrl.is_down() || keys_state.rctrl.is_down();
+		let captures_tab = self.focused.as_ref().is_some_and(|name| self.widgets[name].captures_tab()) && !ctrl;
+		if keys_state.tab.is_pressed() && !captures_tab {
+			let backward = keys_state.lshift.is_down() || keys_state.rshift.is_down();
+			let focusable = self.order.iter().filter(|name| self.is_focusable(name)).cloned().collect::<Vec<String>>();
+			if focusable.is_empty() {
+				return false;
+			}
+			let index = self.focused.as_ref().and_then(|name| focusable.iter().position(|other| other == name));
+			let next = match (index, backward) {
+				(None, false) => 0,
+				(None, true) => focusable.len() - 1,
+				(Some(index), false) => (index + 1) % focusable.len(),
+				(Some(index), true) => (index + focusable.len() - 1) % focusable.len(),
+			};
+			self.focus(Some(&focusable[next]));
+			return true;
+		}
+
+		for (action, direction) in [("ui up", (0, -1)), ("ui down", (0, 1)), ("ui left", (-1, 0)), ("ui right", (1, 0))]
+		{
+			if input.action(action).is_pressed() {
+				if let Some(name) = self.closest_focusable(direction) {
+					self.focus(Some(&name));
+					return true;
+				}
+			}
+		}
+		false
+	}
+
+	/// The focusable widget closest to the focused one in the direction, favoring the aligned ones
+	/// (the first focusable widget if none is focused)
+	fn closest_focusable(&self, direction: (i32, i32)) -> Option<String> {
+		let center = |name: &String| self.widgets[name].get_rect().map(|rect| rect.center());
+		let mut focusable = self.order.iter().filter(|name| self.is_focusable(name));
+		let Some(origin) = self.focused.as_ref().and_then(center) else {
+			return focusable.next().cloned();
+		};
+		focusable
+			.filter(|name| Some(*name) != self.focused.as_ref())
+			.filter_map(|name| center(name).map(|position| (name, position - origin)))
+			.filter_map(|(name, offset)| {
+				let along = offset.x() * direction.0 + offset.y() * direction.1;
+				let across = (offset.x() * direction.1 - offset.y() * direction.0).abs();
+				(along > 0).then_some((name, along + 2 * across))
+			})
+			.min_by_key(|(_, distance)| *distance)
+			.map(|(name, _)| name.clone())
+	}
+
+	fn is_focusable(&self, name: &str) -> bool {
+		self.is_active(name) && self.widgets[name].is_focusable()
+	}
+
+	/// Whether a modal widget (like an open dialog) is blocking the inputs, the disabled ones don't
+	pub fn is_modal_open(&self) -> bool {
+		self.top_modal().is_some()
+	}
+
+	/// The active modal widget drawn above the backdrop and getting the inputs, the last added if several are open
+	fn top_modal(&self) -> Option<&String> {
+		self.order.iter().rev().find(|name| self.is_active(name) && self.widgets[*name].is_modal())
 	}
 
 	pub fn update(&mut self, input: &Input, delta: f64, text_drawer: &mut TextDrawer) -> bool {
 		let mut redraw = false;
-		for widget in self.0.values_mut() {
-			redraw |= widget.update(input, delta, text_drawer);
+		self.cursor = None;
+		self.events.clear();
+		// The values set by the app are shown even by the widgets that are not updated, without events
+		for widget in self.widgets.values_mut() {
+			redraw |= widget.pull_binding();
+		}
+		if let Some(name) = self.top_modal().cloned() {
+			let modal = self.widgets.get_mut(&name).unwrap();
+			redraw |= update_widget(&name, modal, input, delta, text_drawer, &mut self.events);
+			self.drag_drop.cancel();
+		} else {
+			// In the order they were added, so that the events are too
+			let (disabled, hidden) = (&self.disabled, &self.hidden);
+			for name in self.order.iter().filter(|name| !disabled.contains(*name) && !hidden.contains(*name)) {
+				let widget = self.widgets.get_mut(name).unwrap();
+				redraw |= update_widget(name, widget, input, delta, text_drawer, &mut self.events);
+				if let Some((payload, preview)) = widget.take_drag() {
+					self.drag_drop.start_boxed(name, payload, preview);
+				}
+			}
+			redraw |= self.update_focus(input);
+			redraw |= self.update_focus_navigation(input);
+			redraw |= self.update_drag_drop(input);
 		}
+		// Also the disabled and hidden widgets, whose values can be changed by the app
+		for widget in self.widgets.values_mut() {
+			widget.push_binding();
+		}
+		redraw |= self.run_callbacks();
+		redraw |= self.update_tooltip(input, delta);
+		self.update_access_backend();
 		redraw
 	}
 
+	/// The events of the widgets during this frame's update, with the names of the widgets.
+	///
+	/// The events of the updates come in the order the widgets were added, then the changes of values from the focus
+	/// changes and the drops
+	///
+	/// They are cleared at the next update, whether they are taken or not
+	pub fn take_events(&mut self) -> Vec<(String, WidgetEvent)> {
+		std::mem::take(&mut self.events)
+	}
+
+	/// Calls 'callback' with every event of the widget, after the update of the widgets
+	pub fn on_event(&mut self, name: &str, callback: impl FnMut(&WidgetEvent, &mut Widgets) + 'static) {
+		self.callbacks.entry(name.to_string()).or_default().push(Box::new(callback));
+	}
+
+	/// Calls 'callback' when the button is pressed
+	pub fn on_click(&mut self, name: &str, mut callback: impl FnMut(&mut Widgets) + 'static) {
+		self.on_event(name, move |event, widgets| {
+			if let WidgetEvent::Clicked = event {
+				callback(widgets);
+			}
+		});
+	}
+
+	/// Calls 'callback' with the new value when the user changes it
+	pub fn on_change(&mut self, name: &str, mut callback: impl FnMut(&WidgetValue, &mut Widgets) + 'static) {
+		self.on_event(name, move |event, widgets| {
+			if let WidgetEvent::Changed(value) = event {
+				callback(value, widgets);
+			}
+		});
+	}
+
+	/// Calls 'callback' with the new state when the switch is toggled
+	pub fn on_toggle(&mut self, name: &str, mut callback: impl FnMut(bool, &mut Widgets) + 'static) {
+		self.on_event(name, move |event, widgets| {
+			if let WidgetEvent::Toggled(state) = event {
+				callback(*state, widgets);
+			}
+		});
+	}
+
+	/// Calls 'callback' with the content when Enter is pressed in the text input
+	pub fn on_submit(&mut self, name: &str, mut callback: impl FnMut(&str, &mut Widgets) + 'static) {
+		self.on_event(name, move |event, widgets| {
+			if let WidgetEvent::Submitted(text) = event {
+				callback(text, widgets);
+			}
+		});
+	}
+
+	/// Calls the callbacks of the events of this frame, returns true if any was called
+	fn run_callbacks(&mut self) -> bool {
+		if self.callbacks.is_empty() || self.events.is_empty() {
+			return false;
+		}
+		// The callbacks are taken out to lend them the widgets
+		let mut callbacks = std::mem::take(&mut self.callbacks);
+		let mut called = false;
+		for (name, event) in self.events.clone() {
+			for callback in callbacks.get_mut(&name).into_iter().flatten() {
+				callback(&event, self);
+				called = true;
+			}
+		}
+		// Keeps the callbacks registered by the callbacks
+		for (name, mut added) in std::mem::take(&mut self.callbacks) {
+			callbacks.entry(name).or_default().append(&mut added);
+		}
+		self.callbacks = callbacks;
+		called
+	}
+
+	pub fn drag_drop(&self) -> &DragDrop {
+		&self.drag_drop
+	}
+
+	/// To start a drag from the app, to hover and to take the drops of its own targets
+	pub fn drag_drop_mut(&mut self) -> &mut DragDrop {
+		&mut self.drag_drop
+	}
+
+	/// Ends the drag (delivering the drop to the target widget), or sets the widget under the mouse as the target
+	fn update_drag_drop(&mut self, input: &Input) -> bool {
+		let mut redraw = self.drag_drop.update(input);
+		if let Some((target, payload)) = self.drag_drop.get_dropped() {
+			if let Some(widget) = self.widgets.get_mut(target) {
+				redraw |= record_change(target, widget, &mut self.events, |widget| widget.receive_drop(payload));
+			}
+		}
+
+		if let Some(payload) = self.drag_drop.get_any_payload() {
+			let mouse_position = Point::new(input.mouse.position.x, input.mouse.position.y);
+			let source = self.drag_drop.get_source();
+			let target = self
+				.order
+				.iter()
+				.filter(|name| Some(name.as_str()) != source && self.is_visible(name))
+				.find(|name| self.widgets[*name].get_rect().is_some_and(|rect| rect.contains_point(mouse_position)))
+				.map(|name| (name.clone(), self.is_enabled(name) && self.widgets[name].accepts_drop(payload)));
+			if let Some((name, accepted)) = target {
+				self.drag_drop.hover(&name, accepted);
+			}
+			// The preview follows the mouse
+			redraw |= input.mouse.delta != Vector2::zeros();
+		}
+		redraw
+	}
+
+	/// Sets the cursor for this frame (like a grab hand over a draggable object), the hovered widgets take precedence
+	pub fn set_cursor(&mut self, cursor: MouseCursor) {
+		self.cursor = Some(cursor);
+	}
+
+	/// The cursor of the drag, of the modal widget or of the hovered widget, else the one set by the app for this frame
+	pub fn get_cursor(&self) -> MouseCursor {
+		if self.drag_drop.is_dragging() {
+			return match self.drag_drop.get_hovered_target() {
+				Some((_, false)) => MouseCursor::NotAllowed,
+				_ => MouseCursor::Grabbing,
+			};
+		}
+		let cursor = match self.top_modal() {
+			Some(name) => self.widgets[name].get_cursor(),
+			None => self
+				.order
+				.iter()
+				.filter(|name| self.is_active(name))
+				.find_map(|name| self.widgets[name].get_cursor())
+				.or(self.cursor),
+		};
+		cursor.unwrap_or(MouseCursor::Arrow)
+	}
+
+	/// Sets the backend exposing the accessibility tree to the system (or removes it)
+	pub fn set_access_backend(&mut self, backend: Option<Box<dyn AccessBackend>>) {
+		self.access_backend = backend;
+		self.access_tree = None;
+	}
+
+	/// The accessibility tree: a window with the widgets in the Tab traversal order
+	pub fn access_tree(&self) -> AccessNode {
+		let mut root = AccessNode::new(Role::Window, "");
+		for name in self.order.iter().filter(|name| self.is_visible(name)) {
+			let widget = &self.widgets[name];
+			let Some(mut node) = widget.access_node() else {
+				continue;
+			};
+			node.disabled = !self.is_enabled(name);
+			node.id = name.clone();
+			if node.label.is_empty() {
+				node.label = name.clone();
+			}
+			node.focused |= widget.is_focused();
+			node.modal |= widget.is_modal();
+			node.bounds = node.bounds.or(widget.get_rect());
+			node.assign_child_ids();
+			root.children.push(node);
+		}
+		root
+	}
+
+	/// Sends the accessibility tree to the backend if it changed
+	fn update_access_backend(&mut self) {
+		if self.access_backend.is_none() {
+			return;
+		}
+		let tree = self.access_tree();
+		if self.access_tree.as_ref() != Some(&tree) {
+			if let Some(backend) = &mut self.access_backend {
+				backend.update_tree(&tree);
+			}
+			self.access_tree = Some(tree);
+		}
+	}
+
+	/// Updates the hover timer of the tooltips, returns true if a tooltip appeared or disappeared
+	fn update_tooltip(&mut self, input: &Input, delta: f64) -> bool {
+		let was_visible = self.visible_tooltip().is_some();
+		self.mouse_position = Point::new(input.mouse.position.x, input.mouse.position.y);
+
+		let hovered_name = if self.is_modal_open() {
+			None
+		} else {
+			self.tooltips
+				.keys()
+				.filter(|name| self.is_active(name))
+				.find(|name| self.widgets.get(*name).is_some_and(|widget| widget.is_hovered()))
+		};
+		self.tooltip_hover = match (hovered_name, self.tooltip_hover.take()) {
+			(Some(name), Some((hovered_name, time))) if *name == hovered_name => Some((hovered_name, time + delta)),
+			(Some(name), _) => Some((name.clone(), 0.0)),
+			(None, _) => None,
+		};
+
+		let is_visible = self.visible_tooltip().is_some();
+		// The tooltip follows the mouse
+		was_visible != is_visible || (is_visible && input.mouse.delta != Vector2::zeros())
+	}
+
+	fn visible_tooltip(&self) -> Option<&Tooltip> {
+		let (name, time) = self.tooltip_hover.as_ref()?;
+		self.tooltips.get(name).filter(|tooltip| *time >= tooltip.delay())
+	}
+
 	pub fn draw(&self, canvas: &mut Canvas<Window>, text_drawer: &TextDrawer) {
-		for widget in self.0.values() {
+		// The other open modals are drawn below the backdrop, like the widgets they block
+		let top_modal = self.top_modal();
+		for name in self.order.iter().filter(|name| self.is_visible(name) && Some(*name) != top_modal) {
+			let widget = &self.widgets[name];
 			widget.draw(canvas, text_drawer);
+			if !self.is_enabled(name) {
+				if let Some(rect) = widget.get_rect() {
+					let style = &self.disabled_style;
+					fill_rounded_rect(canvas, rect, style.overlay_color, style.corner_radius);
+				}
+			}
+		}
+		if let Some(rect) = self.focused.as_ref().and_then(|name| self.widgets[name].get_rect()) {
+			let style = &self.focus_style;
+			for offset in 0..style.thickness as i32 {
+				let margin = style.margin + offset;
+				let ring = Rect::new(
+					rect.left() - margin,
+					rect.top() - margin,
+					rect.width() + 2 * margin as u32,
+					rect.height() + 2 * margin as u32,
+				);
+				draw_rounded_rect(canvas, ring, style.color, style.corner_radius + offset as u16);
+			}
+		}
+		if let Some(name) = top_modal {
+			canvas.set_blend_mode(BlendMode::Blend);
+			canvas.set_draw_color(BACKDROP);
+			canvas.fill_rect(None).unwrap();
+			canvas.set_blend_mode(BlendMode::None);
+			self.widgets[name].draw(canvas, text_drawer);
+		}
+		if let Some(tooltip) = self.visible_tooltip() {
+			tooltip.draw(canvas, text_drawer, self.mouse_position);
 		}
+		self.drag_drop.draw(canvas, text_drawer, self.mouse_position);
 	}
 
 	// TODO: remove this and replace with a macro that right all the code for us
@@ -98,4 +805,12 @@ impl Widgets {
 			panic!("Slider '{}' not found", name);
 		}
 	}
+
+	pub fn get_mut_dialog(&mut self, name: &str) -> &mut Dialog {
+		if let Some(dialog) = self.get_mut(name) {
+			dialog
+		} else {
+			panic!("Dialog '{}' not found", name);
+		}
+	}
 }
@@ -0,0 +1,324 @@
+use crate::canvas::{draw_rect, fill_rect};
+use crate::input::MouseCursor;
+use crate::prelude::*;
+use crate::widgets::binding::BoundValue;
+use crate::widgets::{AccessNode, Binding, Role, WidgetValue, HOVER};
+
+/// A number input holds either an **integer** or a **float** written with **precision** decimals
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum NumberType {
+	Integer,
+	Float { precision: usize },
+}
+
+/// A number input is a text input restricted to numbers, with increment / decrement arrows.
+///
+/// The arrows and the mouse wheel step the value, even while typing. When it's not focused, the value can also be
+/// changed by dragging horizontally over it. A simple click focuses it to type the value
+pub struct NumberInput {
+	rect: Rect,
+	text_input: TextInput,
+	number_type: NumberType,
+	value: f64,
+	min: f64,
+	max: f64,
+	step: f64,
+	/// Unit written after the value, like "kg" or "N/m"
+	suffix: Option<String>,
+	hovered: bool,
+	up_hovered: bool,
+	down_hovered: bool,
+	/// Mouse x position and value when the left button was pressed over the text
+	drag_start: Option<(i32, f64)>,
+	dragging: bool,
+	binding: Option<BoundValue<f64>>,
+}
+
+impl NumberInput {
+	const ARROWS_WIDTH: u32 = 18;
+	/// Distance the mouse has to move before a click becomes a drag
+	const DRAG_THRESHOLD: i32 = 3;
+	/// Distance the mouse has to be dragged to change the value by one step
+	const PIXELS_PER_STEP: f64 = 4.0;
+
+	/// The bounds are swapped if 'min' is greater than 'max'
+	pub fn new(
+		rect: Rect, number_type: NumberType, value: f64, min: f64, max: f64, step: f64, suffix: Option<&str>,
+	) -> Self {
+		assert!(!min.is_nan() && !max.is_nan(), "The bounds of a number input can't be NaN");
+		let (min, max) = if min <= max { (min, max) } else { (max, min) };
+		let mut text_input = TextInput::new(Self::text_rect(rect), None, None);
+		text_input.set_validator(Some(match number_type {
+			NumberType::Integer => Validator::integer(),
+			NumberType::Float { .. } => Validator::float(),
+		}));
+		let mut number_input = Self {
+			rect,
+			text_input,
+			number_type,
+			value: 0.0,
+			min,
+			max,
+			step,
+			suffix: suffix.map(|suffix| suffix.to_string()),
+			hovered: false,
+			up_hovered: false,
+			down_hovered: false,
+			drag_start: None,
+			dragging: false,
+			binding: None,
+		};
+		number_input.set_value(value);
+		number_input
+	}
+
+	pub fn get_value(&self) -> f64 {
+		self.value
+	}
+
+	/// Sets the value, clamped between min and max and rounded to the precision
+	pub fn set_value(&mut self, value: f64) {
+		let value = value.clamp(self.min, self.max);
+		self.value = match self.number_type {
+			NumberType::Integer => value.round(),
+			NumberType::Float { precision } => {
+				let factor = 10_f64.powi(precision as i32);
+				(value * factor).round() / factor
+			}
+		};
+		if !self.text_input.is_focused() {
+			self.text_input.set_content(self.display());
+		}
+	}
+
+	/// Binds the value to the app, the number input takes the value of the binding first
+	/// (clamped and rounded like `set_value`)
+	pub fn set_binding(&mut self, binding: Option<Binding<f64>>) {
+		self.binding = binding.map(BoundValue::new);
+	}
+
+	/// The value as written when the input is not focused (with the suffix)
+	fn display(&self) -> String {
+		match &self.suffix {
+			Some(suffix) => format!("{} {}", self.format_value(), suffix),
+			None => self.format_value(),
+		}
+	}
+
+	fn format_value(&self) -> String {
+		match self.number_type {
+			NumberType::Integer => format!("{:.0}", self.value),
+			NumberType::Float { precision } => format!("{:.*}", precision, self.value),
+		}
+	}
+
+	/// Part of 'rect' left of the arrows
+	fn text_rect(rect: Rect) -> Rect {
+		rect!(rect.left(), rect.top(), rect.width().saturating_sub(Self::ARROWS_WIDTH), rect.height())
+	}
+
+	fn up_arrow_rect(&self) -> Rect {
+		rect!(
+			self.rect.right() - Self::ARROWS_WIDTH as i32,
+			self.rect.top(),
+			Self::ARROWS_WIDTH,
+			self.rect.height() / 2
+		)
+	}
+
+	fn down_arrow_rect(&self) -> Rect {
+		let height = self.rect.height() / 2;
+		rect!(
+			self.rect.right() - Self::ARROWS_WIDTH as i32,
+			self.rect.bottom() - height as i32,
+			Self::ARROWS_WIDTH,
+			height
+		)
+	}
+
+	/// Parses the typed text, and writes back the value with its suffix
+	fn commit_text(&mut self) {
+		if let Ok(value) = self.text_input.content.trim().parse::<f64>() {
+			self.value = value;
+		}
+		self.text_input.set_focused(false);
+		self.set_value(self.value);
+	}
+
+	/// Changes the value by a number of steps, from the typed value while it's focused
+	fn step_value(&mut self, steps: f64) {
+		let typing = self.text_input.is_focused();
+		if let Some(value) = self.text_input.content.trim().parse::<f64>().ok().filter(|_| typing) {
+			self.value = value;
+		}
+		self.set_value(self.value + steps * self.step);
+		if typing {
+			self.text_input.set_content(self.format_value());
+		}
+	}
+
+	fn draw_arrow(canvas: &mut Canvas<Window>, rect: Rect, up: bool, color: Color) {
+		let center = rect.center();
+		let (x, y) = (center.x as i16, center.y as i16);
+		let (tip, base) = if up { (y - 3, y + 2) } else { (y + 3, y - 2) };
+		DrawRenderer::filled_trigon(canvas, x - 4, base, x + 4, base, x, tip, color).unwrap();
+	}
+}
+
+impl Widget for NumberInput {
+	fn update(&mut self, input: &Input, delta: f64, text_drawer: &mut TextDrawer) -> bool {
+		let mut changed = false;
+
+		let mouse_position = Point::new(input.mouse.position.x, input.mouse.position.y);
+		let hovered = self.rect.contains_point(mouse_position);
+		if hovered != self.hovered {
+			self.hovered = hovered;
+			changed = true;
+		}
+		let up_hovered = !self.dragging && self.up_arrow_rect().contains_point(mouse_position);
+		let down_hovered = !self.dragging && self.down_arrow_rect().contains_point(mouse_position);
+		if (up_hovered, down_hovered) != (self.up_hovered, self.down_hovered) {
+			self.up_hovered = up_hovered;
+			self.down_hovered = down_hovered;
+			changed = true;
+		}
+
+		// Typing
+		if self.text_input.is_focused() {
+			changed |= self.text_input.update(input, delta, text_drawer);
+			// A click on the arrows steps the typed value, the input stays focused
+			if input.mouse.left_button.is_pressed() && (self.up_hovered || self.down_hovered) {
+				self.text_input.set_focused(true);
+			}
+			if !self.text_input.is_focused() || self.text_input.is_submitted() {
+				self.commit_text();
+				changed = true;
+			}
+		}
+
+		// Arrows
+		if input.mouse.left_button.is_pressed() {
+			if self.up_hovered {
+				self.step_value(1.0);
+				changed = true;
+			} else if self.down_hovered {
+				self.step_value(-1.0);
+				changed = true;
+			} else if !self.text_input.is_focused() && Self::text_rect(self.rect).contains_point(mouse_position) {
+				self.drag_start = Some((mouse_position.x, self.value));
+			}
+		}
+
+		// Dragging
+		if let Some((start_x, start_value)) = self.drag_start {
+			let distance = mouse_position.x - start_x;
+			if distance.abs() > Self::DRAG_THRESHOLD {
+				self.dragging = true;
+			}
+			if self.dragging {
+				let value = start_value + (distance as f64 / Self::PIXELS_PER_STEP).round() * self.step;
+				if value != self.value {
+					self.set_value(value);
+					changed = true;
+				}
+			}
+			if input.mouse.left_button.is_released() {
+				// A click without drag focuses the input to type the value
+				if !self.dragging {
+					self.text_input.set_content(self.format_value());
+					self.text_input.set_focused(true);
+				}
+				self.drag_start = None;
+				self.dragging = false;
+				changed = true;
+			}
+		}
+
+		// Scrolling
+		if self.hovered && input.mouse.wheel.y != 0 {
+			self.step_value(input.mouse.wheel.y as f64);
+			changed = true;
+		}
+
+		changed
+	}
+
+	fn draw(&self, canvas: &mut Canvas<Window>, text_drawer: &TextDrawer) {
+		self.text_input.draw(canvas, text_drawer);
+
+		for (rect, up, hovered) in
+			[(self.up_arrow_rect(), true, self.up_hovered), (self.down_arrow_rect(), false, self.down_hovered)]
+		{
+			fill_rect(canvas, rect, if hovered { darker(Colors::LIGHT_GREY, HOVER) } else { Colors::LIGHT_GREY });
+			draw_rect(canvas, rect, Colors::BLACK);
+			Self::draw_arrow(canvas, rect, up, Colors::BLACK);
+		}
+	}
+
+	fn is_hovered(&self) -> bool {
+		self.hovered
+	}
+	fn is_focusable(&self) -> bool {
+		true
+	}
+
+	fn is_focused(&self) -> bool {
+		self.text_input.is_focused()
+	}
+
+	fn set_focused(&mut self, focused: bool) {
+		if focused && !self.text_input.is_focused() {
+			self.text_input.set_content(self.format_value());
+			self.text_input.set_focused(true);
+		} else if !focused && self.text_input.is_focused() {
+			self.commit_text();
+		}
+	}
+
+	fn get_rect(&self) -> Option<Rect> {
+		Some(self.rect)
+	}
+
+	fn access_node(&self) -> Option<AccessNode> {
+		Some(AccessNode { value: Some(self.display()), ..AccessNode::new(Role::NumberInput, "") })
+	}
+
+	/// Left-right arrows where the value can be dragged, a hand over the arrow buttons
+	fn get_cursor(&self) -> Option<MouseCursor> {
+		if self.dragging {
+			Some(MouseCursor::ResizeHorizontal)
+		} else if self.up_hovered || self.down_hovered {
+			Some(MouseCursor::Hand)
+		} else if self.text_input.is_focused() {
+			self.text_input.get_cursor()
+		} else {
+			self.hovered.then_some(MouseCursor::ResizeHorizontal)
+		}
+	}
+
+	fn reset_interaction(&mut self) {
+		self.text_input.reset_interaction();
+		self.hovered = false;
+		(self.up_hovered, self.down_hovered) = (false, false);
+		self.drag_start = None;
+		self.dragging = false;
+	}
+
+	fn get_widget_value(&self) -> Option<WidgetValue> {
+		Some(WidgetValue::Number(self.value))
+	}
+
+	fn pull_binding(&mut self) -> bool {
+		let Some(value) = self.binding.as_mut().and_then(|binding| binding.pull()) else {
+			return false;
+		};
+		self.set_value(value);
+		true
+	}
+
+	fn push_binding(&mut self) {
+		if let Some(binding) = &mut self.binding {
+			binding.push(&self.value);
+		}
+	}
+}
@@ -0,0 +1,197 @@
+use crate::canvas::{draw_rounded_rect, fill_rounded_rect};
+use crate::prelude::*;
+use crate::widgets::{AccessNode, Role};
+use sdl2::ttf::FontStyle;
+
+/// The progress of a progress bar can be:
+///
+/// **determinate** (from 0.0 to 1.0) or **indeterminate** (when the duration of the task is unknown)
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum Progress {
+	Determinate(f32),
+	Indeterminate,
+}
+
+pub struct ProgressBarStyle {
+	color: Color,
+	back_color: Color,
+	contour_color: Color,
+	corner_radius: u16,
+	/// If some, the percentage is written on the bar
+	text_style: Option<TextStyle>,
+}
+
+impl ProgressBarStyle {
+	pub fn new(color: Color, corner_radius: u16, text_style: Option<TextStyle>) -> Self {
+		Self {
+			color,
+			back_color: darker(paler(color, 0.5), 0.9),
+			contour_color: Colors::BLACK,
+			corner_radius,
+			text_style,
+		}
+	}
+}
+
+impl Default for ProgressBarStyle {
+	fn default() -> Self {
+		Self::new(Colors::ROYAL_BLUE, 4, Some(TextStyle::new(16, None, Color::BLACK, FontStyle::NORMAL)))
+	}
+}
+
+/// A progress bar shows the progress of a task.
+///
+/// Its orientation (horizontal or vertical) is deduced from its rect, like the slider's
+pub struct ProgressBar {
+	rect: Rect,
+	orientation: Orientation,
+	style: ProgressBarStyle,
+	progress: Progress,
+	/// Progress actually drawn, it moves smoothly toward the real progress (0.0 - 1.0)
+	displayed_value: f32,
+	/// Position of the moving chunk of an indeterminate progress bar (0.0 - 2.0, back and forth)
+	phase: f32,
+	hovered: bool,
+}
+
+impl ProgressBar {
+	/// Time (in seconds) for the chunk of an indeterminate progress bar to cross the bar
+	const CROSSING_TIME: f32 = 1.2;
+	/// Size of the moving chunk of an indeterminate progress bar (relative to the bar length)
+	const CHUNK_SIZE: f32 = 0.3;
+	/// Speed at which the displayed value reaches the real one
+	const SMOOTHING: f32 = 10.0;
+
+	pub fn new(rect: Rect, style: Option<ProgressBarStyle>, progress: Progress) -> Self {
+		Self {
+			rect,
+			orientation: Orientation::from_rect(rect),
+			style: style.unwrap_or_default(),
+			progress,
+			displayed_value: match progress {
+				Progress::Determinate(value) => value.clamp(0.0, 1.0),
+				Progress::Indeterminate => 0.0,
+			},
+			phase: 0.0,
+			hovered: false,
+		}
+	}
+
+	pub fn get_progress(&self) -> Progress {
+		self.progress
+	}
+
+	pub fn set_progress(&mut self, progress: Progress) {
+		self.progress = match progress {
+			Progress::Determinate(value) => Progress::Determinate(value.clamp(0.0, 1.0)),
+			Progress::Indeterminate => Progress::Indeterminate,
+		};
+	}
+
+	/// Sets a determinate progress (0.0 - 1.0)
+	pub fn set_value(&mut self, value: f32) {
+		self.set_progress(Progress::Determinate(value));
+	}
+
+	pub fn is_complete(&self) -> bool {
+		self.progress == Progress::Determinate(1.0)
+	}
+
+	fn length(&self) -> u32 {
+		match self.orientation {
+			Orientation::Horizontal => self.rect.width(),
+			Orientation::Vertical => self.rect.height(),
+		}
+	}
+
+	/// Rect of the filled part of the bar, from 'start' to 'end' (0.0 - 1.0)
+	fn fill_rect(&self, start: f32, end: f32) -> Rect {
+		let start = (start * self.length() as f32) as u32;
+		let end = (end * self.length() as f32) as u32;
+		match self.orientation {
+			Orientation::Horizontal => {
+				rect!(self.rect.left() + start as i32, self.rect.top(), end - start, self.rect.height())
+			}
+			Orientation::Vertical => {
+				rect!(self.rect.left(), self.rect.bottom() - end as i32, self.rect.width(), end - start)
+			}
+		}
+	}
+}
+
+impl Widget for ProgressBar {
+	fn update(&mut self, input: &Input, delta: f64, _text_drawer: &mut TextDrawer) -> bool {
+		let mut changed = false;
+
+		let mouse_position = Point::new(input.mouse.position.x, input.mouse.position.y);
+		let hovered = self.rect.contains_point(mouse_position);
+		if hovered != self.hovered {
+			self.hovered = hovered;
+			changed = true;
+		}
+
+		match self.progress {
+			Progress::Determinate(value) => {
+				if self.displayed_value != value {
+					let step = (value - self.displayed_value) * (Self::SMOOTHING * delta as f32).min(1.0);
+					self.displayed_value =
+						if (value - self.displayed_value).abs() < 0.001 { value } else { self.displayed_value + step };
+					changed = true;
+				}
+			}
+			Progress::Indeterminate => {
+				self.phase = (self.phase + delta as f32 / Self::CROSSING_TIME) % 2.0;
+				changed = true;
+			}
+		}
+
+		changed
+	}
+
+	fn draw(&self, canvas: &mut Canvas<Window>, text_drawer: &TextDrawer) {
+		let style = &self.style;
+		fill_rounded_rect(canvas, self.rect, style.back_color, style.corner_radius);
+
+		let (start, end) = match self.progress {
+			Progress::Determinate(_) => (0.0, self.displayed_value),
+			Progress::Indeterminate => {
+				// The chunk goes back and forth
+				let position = if self.phase < 1.0 { self.phase } else { 2.0 - self.phase };
+				let start = position * (1.0 - Self::CHUNK_SIZE);
+				(start, start + Self::CHUNK_SIZE)
+			}
+		};
+		if end > start {
+			let rect = self.fill_rect(start, end);
+			if rect.width() > 2 * style.corner_radius as u32 && rect.height() > 2 * style.corner_radius as u32 {
+				fill_rounded_rect(canvas, rect, style.color, style.corner_radius);
+			}
+		}
+		draw_rounded_rect(canvas, self.rect, style.contour_color, style.corner_radius);
+
+		if let (Some(text_style), Progress::Determinate(_)) = (&style.text_style, self.progress) {
+			let text = format!("{:.0}%", self.displayed_value * 100.0);
+			text_drawer.draw(canvas, self.rect.center(), text_style, &text, Align::Center);
+		}
+	}
+
+	fn is_hovered(&self) -> bool {
+		self.hovered
+	}
+
+	fn get_rect(&self) -> Option<Rect> {
+		Some(self.rect)
+	}
+
+	fn access_node(&self) -> Option<AccessNode> {
+		let (value, busy) = match self.progress {
+			Progress::Determinate(value) => (Some(format!("{:.0}%", value * 100.0)), !self.is_complete()),
+			Progress::Indeterminate => (None, true),
+		};
+		Some(AccessNode { value, busy, ..AccessNode::new(Role::ProgressBar, "") })
+	}
+
+	fn reset_interaction(&mut self) {
+		self.hovered = false;
+	}
+}
@@ -1,6 +1,7 @@
-use crate::input::KeyState;
+use crate::input::{KeyState, MouseCursor};
 use crate::prelude::*;
-use crate::widgets::{HOVER, PUSH};
+use crate::widgets::binding::BoundValue;
+use crate::widgets::{AccessNode, Binding, Role, WidgetValue, HOVER, PUSH};
 use sdl2::pixels::Color;
 use sdl2::rect::Rect;
 use sdl2::render::Canvas;
@@ -9,11 +10,6 @@ use sdl2::ttf::FontStyle;
 use crate::canvas::{draw_rounded_rect, fill_rounded_rect};
 use sdl2::video::Window;
 
-pub enum Orientation {
-	Horizontal,
-	Vertical,
-}
-
 /// A slider can be:
 ///
 /// **discrete** (with a number of **snap** points) or **continuous**
@@ -42,21 +38,21 @@ pub struct Slider {
 	orientation: Orientation,
 	corner_radius: u16,
 	hovered: bool,
+	focused: bool,
 	pub state: KeyState,
 	/// Internal value of the slider (0.0 - 1.0)
 	value: f32,
 	slider_type: SliderType,
+	/// Shares the value returned by `get_value`
+	binding: Option<BoundValue<f32>>,
 }
 
 impl Slider {
+	/// Change of a continuous slider value when an arrow key is pressed
+	const KEY_STEP: f32 = 0.05;
+
 	pub fn new(color: Color, rect: Rect, corner_radius: u16, slider_type: SliderType) -> Self {
-		let orientation = {
-			if rect.width() > rect.height() {
-				Orientation::Horizontal
-			} else {
-				Orientation::Vertical
-			}
-		};
+		let orientation = Orientation::from_rect(rect);
 		let thumb_color = Colors::LIGHT_GREY;
 		let back_color = darker(paler(color, 0.5), 0.9);
 		Self {
@@ -71,12 +67,14 @@ impl Slider {
 			orientation,
 			corner_radius,
 			hovered: false,
+			focused: false,
 			state: KeyState::new(),
 			value: match slider_type {
 				SliderType::Discrete { default_value, snap, .. } => default_value as f32 / snap as f32,
 				SliderType::Continuous { default_value, .. } => default_value,
 			},
 			slider_type,
+			binding: None,
 		}
 	}
 
@@ -99,6 +97,11 @@ impl Slider {
 		self.value = value;
 	}
 
+	/// Binds the value (as returned by `get_value`) to the app, the slider takes the value of the binding first
+	pub fn set_binding(&mut self, binding: Option<Binding<f32>>) {
+		self.binding = binding.map(BoundValue::new);
+	}
+
 	fn thumb_position(&self) -> u32 {
 		(self.value * self.length() as f32) as u32
 	}
@@ -159,6 +162,24 @@ impl Widget for Slider {
 			}
 		}
 
+		// The focused slider is adjusted with the arrow keys
+		if self.focused {
+			let keys_state = &input.keys_state;
+			let direction = (keys_state.right.is_pressed() || keys_state.up.is_pressed()) as i32
+				- (keys_state.left.is_pressed() || keys_state.down.is_pressed()) as i32;
+			if direction != 0 {
+				let step = match self.slider_type {
+					SliderType::Discrete { snap, .. } => 1.0 / snap as f32,
+					SliderType::Continuous { .. } => Self::KEY_STEP,
+				};
+				let value = (self.value + direction as f32 * step).clamp(0.0, 1.0);
+				if value != self.value {
+					self.value = value;
+					changed = true;
+				}
+			}
+		}
+
 		changed
 	}
 
@@ -264,4 +285,64 @@ impl Widget for Slider {
 			}
 		}
 	}
+
+	fn is_hovered(&self) -> bool {
+		self.hovered
+	}
+
+	fn is_focusable(&self) -> bool {
+		true
+	}
+
+	fn is_focused(&self) -> bool {
+		self.focused
+	}
+
+	fn set_focused(&mut self, focused: bool) {
+		self.focused = focused;
+	}
+
+	fn get_rect(&self) -> Option<Rect> {
+		Some(self.rect)
+	}
+
+	fn access_node(&self) -> Option<AccessNode> {
+		let value = match self.slider_type {
+			SliderType::Discrete { .. } => format!("{}", self.get_value()),
+			SliderType::Continuous { .. } => format!("{:.2}", self.get_value()),
+		};
+		Some(AccessNode { value: Some(value), ..AccessNode::new(Role::Slider, "") })
+	}
+
+	fn get_cursor(&self) -> Option<MouseCursor> {
+		(self.hovered || self.state.is_down()).then_some(MouseCursor::Hand)
+	}
+
+	fn reset_interaction(&mut self) {
+		self.hovered = false;
+		self.state = KeyState::Up;
+	}
+
+	fn get_widget_value(&self) -> Option<WidgetValue> {
+		Some(WidgetValue::Number(self.get_value() as f64))
+	}
+
+	fn pull_binding(&mut self) -> bool {
+		let Some(value) = self.binding.as_mut().and_then(|binding| binding.pull()) else {
+			return false;
+		};
+		let value = match self.slider_type {
+			SliderType::Discrete { snap, .. } => value.round() / snap as f32,
+			SliderType::Continuous { .. } => value,
+		};
+		self.value = value.clamp(0.0, 1.0);
+		true
+	}
+
+	fn push_binding(&mut self) {
+		let value = self.get_value();
+		if let Some(binding) = &mut self.binding {
+			binding.push(&value);
+		}
+	}
 }
@@ -0,0 +1,115 @@
+use crate::prelude::*;
+use crate::widgets::{AccessNode, Role};
+use std::f64::consts::TAU;
+
+pub struct SpinnerStyle {
+	color: Color,
+	/// Number of dots around the spinner
+	dot_count: u32,
+	/// Number of turns per second
+	speed: f64,
+}
+
+impl SpinnerStyle {
+	/// A spinner has at least one dot
+	pub fn new(color: Color, dot_count: u32, speed: f64) -> Self {
+		Self { color, dot_count: dot_count.max(1), speed }
+	}
+}
+
+impl Default for SpinnerStyle {
+	fn default() -> Self {
+		Self::new(Colors::ROYAL_BLUE, 10, 1.0)
+	}
+}
+
+/// A spinner shows that a task of unknown duration is running.
+///
+/// It is made of dots turning around the center of its rect, and is only drawn while running
+pub struct Spinner {
+	rect: Rect,
+	style: SpinnerStyle,
+	running: bool,
+	/// Whether it was started or stopped since the last update, to redraw it
+	running_changed: bool,
+	/// Angle of the leading dot (in turns, 0.0 - 1.0)
+	phase: f64,
+	hovered: bool,
+}
+
+impl Spinner {
+	pub fn new(rect: Rect, style: Option<SpinnerStyle>, running: bool) -> Self {
+		Self { rect, style: style.unwrap_or_default(), running, running_changed: false, phase: 0.0, hovered: false }
+	}
+
+	pub fn is_running(&self) -> bool {
+		self.running
+	}
+
+	pub fn set_running(&mut self, running: bool) {
+		self.running_changed |= running != self.running;
+		self.running = running;
+	}
+
+	fn radius(&self) -> f64 {
+		self.rect.width().min(self.rect.height()) as f64 / 2.0
+	}
+}
+
+impl Widget for Spinner {
+	fn update(&mut self, input: &Input, delta: f64, _text_drawer: &mut TextDrawer) -> bool {
+		let mut changed = std::mem::take(&mut self.running_changed);
+
+		let mouse_position = Point::new(input.mouse.position.x, input.mouse.position.y);
+		let hovered = self.rect.contains_point(mouse_position);
+		if hovered != self.hovered {
+			self.hovered = hovered;
+			changed = true;
+		}
+
+		if self.running {
+			self.phase = (self.phase + delta * self.style.speed) % 1.0;
+			changed = true;
+		}
+
+		changed
+	}
+
+	fn draw(&self, canvas: &mut Canvas<Window>, _text_drawer: &TextDrawer) {
+		if !self.running {
+			return;
+		}
+		let center = self.rect.center();
+		let dot_radius = self.radius() / 6.0;
+		let orbit_radius = self.radius() - dot_radius;
+		let dot_count = self.style.dot_count;
+
+		// The dots fade behind the leading one
+		let leading_dot = (self.phase * dot_count as f64) as u32;
+		for dot in 0..dot_count {
+			let angle = TAU * dot as f64 / dot_count as f64;
+			let x = center.x as f64 + orbit_radius * angle.cos();
+			let y = center.y as f64 + orbit_radius * angle.sin();
+			let age = (leading_dot + dot_count - dot) % dot_count;
+			let mut color = self.style.color;
+			color.a = (255 - 255 * age / dot_count) as u8;
+			DrawRenderer::filled_circle(canvas, x as i16, y as i16, dot_radius as i16, color).unwrap();
+		}
+	}
+
+	fn is_hovered(&self) -> bool {
+		self.hovered
+	}
+
+	fn get_rect(&self) -> Option<Rect> {
+		Some(self.rect)
+	}
+
+	fn access_node(&self) -> Option<AccessNode> {
+		Some(AccessNode { busy: self.running, ..AccessNode::new(Role::Spinner, "") })
+	}
+
+	fn reset_interaction(&mut self) {
+		self.hovered = false;
+	}
+}
@@ -1,6 +1,7 @@
-use crate::input::KeyState;
+use crate::input::{KeyState, MouseCursor};
 use crate::prelude::*;
-use crate::widgets::{HOVER, PUSH};
+use crate::widgets::binding::BoundValue;
+use crate::widgets::{activation_state, AccessNode, Binding, Role, WidgetEvent, HOVER, PUSH};
 use sdl2::pixels::Color;
 use sdl2::rect::Rect;
 use sdl2::render::Canvas;
@@ -21,19 +22,15 @@ pub struct Switch {
 	orientation: Orientation,
 	corner_radius: u16,
 	hovered: bool,
+	focused: bool,
 	pub state: KeyState,
 	switched: bool,
+	binding: Option<BoundValue<bool>>,
 }
 
 impl Switch {
 	pub fn new(on_color: Color, off_color: Color, rect: Rect, corner_radius: u16) -> Self {
-		let orientation = {
-			if rect.width() > rect.height() {
-				Orientation::Horizontal
-			} else {
-				Orientation::Vertical
-			}
-		};
+		let orientation = Orientation::from_rect(rect);
 		let thumb_color = Colors::LIGHT_GREY;
 		Self {
 			on_color,
@@ -46,8 +43,10 @@ impl Switch {
 			orientation,
 			corner_radius,
 			hovered: false,
+			focused: false,
 			state: KeyState::new(),
 			switched: false,
+			binding: None,
 		}
 	}
 
@@ -59,6 +58,11 @@ impl Switch {
 		self.switched
 	}
 
+	/// Binds the state to the app, the switch takes the state of the binding first
+	pub fn set_binding(&mut self, binding: Option<Binding<bool>>) {
+		self.binding = binding.map(BoundValue::new);
+	}
+
 	fn thumb_position(&self) -> u32 {
 		self.switched as u32 * self.length()
 	}
@@ -83,10 +87,12 @@ impl Widget for Switch {
 			changed = true;
 		}
 
-		if input.mouse.left_button.is_pressed() && self.hovered {
+		// The focused switch is also toggled with Enter, Space or the gamepad
+		let activation = if self.focused { activation_state(input) } else { KeyState::Up };
+		if (input.mouse.left_button.is_pressed() && self.hovered) || activation.is_pressed() {
 			self.state.press();
 			changed = true;
-		} else if self.state.is_down() && input.mouse.left_button.is_released() {
+		} else if self.state.is_down() && (input.mouse.left_button.is_released() || activation.is_released()) {
 			self.state.release();
 			changed = true;
 		}
@@ -149,4 +155,59 @@ impl Widget for Switch {
 		fill_rounded_rect(canvas, thumb_rect, color, radius);
 		draw_rounded_rect(canvas, thumb_rect, Colors::BLACK, radius);
 	}
+
+	fn is_hovered(&self) -> bool {
+		self.hovered
+	}
+
+	fn is_focusable(&self) -> bool {
+		true
+	}
+
+	fn is_focused(&self) -> bool {
+		self.focused
+	}
+
+	fn set_focused(&mut self, focused: bool) {
+		self.focused = focused;
+	}
+
+	fn get_rect(&self) -> Option<Rect> {
+		Some(self.rect)
+	}
+
+	fn access_node(&self) -> Option<AccessNode> {
+		Some(AccessNode { checked: Some(self.switched), ..AccessNode::new(Role::Switch, "") })
+	}
+
+	fn get_cursor(&self) -> Option<MouseCursor> {
+		self.hovered.then_some(MouseCursor::Hand)
+	}
+
+	fn reset_interaction(&mut self) {
+		self.hovered = false;
+		self.state = KeyState::Up;
+	}
+
+	fn get_events(&self) -> Vec<WidgetEvent> {
+		if self.state.is_pressed() {
+			vec![WidgetEvent::Toggled(self.switched)]
+		} else {
+			Vec::new()
+		}
+	}
+
+	fn pull_binding(&mut self) -> bool {
+		let Some(switched) = self.binding.as_mut().and_then(|binding| binding.pull()) else {
+			return false;
+		};
+		self.switched = switched;
+		true
+	}
+
+	fn push_binding(&mut self) {
+		if let Some(binding) = &mut self.binding {
+			binding.push(&self.switched);
+		}
+	}
 }
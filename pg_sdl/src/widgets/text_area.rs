@@ -0,0 +1,601 @@
+use crate::canvas::{draw_rect, fill_rect};
+use crate::input::MouseCursor;
+use crate::prelude::*;
+use crate::widgets::{AccessNode, Role, WidgetValue};
+use std::ops::Range;
+
+/// Function coloring a line of a text area, it returns the char ranges to color.
+///
+/// The chars that are not in any range keep the color of the text style
+pub type Highlighter = Box<dyn Fn(&str) -> Vec<(Range<usize>, Color)>>;
+
+pub struct TextAreaStyle {
+	background_color: Color,
+	contour_color: Color,
+	contour_focused_color: Color,
+	gutter_color: Color,
+	line_number_color: Color,
+	scrollbar_color: Color,
+	text_style: TextStyle,
+	line_numbers: bool,
+	soft_wrap: bool,
+	/// Number of spaces inserted by the tab key
+	tab_size: usize,
+}
+
+impl TextAreaStyle {
+	pub fn new(text_style: TextStyle, line_numbers: bool, soft_wrap: bool, tab_size: usize) -> Self {
+		Self {
+			background_color: Colors::WHITE,
+			contour_color: Colors::BLACK,
+			contour_focused_color: paler(Colors::BLUE, 0.9),
+			gutter_color: darker(Colors::WHITE, 0.92),
+			line_number_color: Colors::GREY,
+			scrollbar_color: Colors::LIGHT_GREY,
+			text_style,
+			line_numbers,
+			soft_wrap,
+			tab_size: tab_size.max(1),
+		}
+	}
+}
+
+impl Default for TextAreaStyle {
+	fn default() -> Self {
+		Self::new(TextStyle::default(), true, true, 4)
+	}
+}
+
+/// Part of a line drawn on one row of the text area (a line is cut in several rows when soft wrapped)
+struct Row {
+	line: usize,
+	/// Char range of the row in the line
+	start: usize,
+	end: usize,
+}
+
+/// A text area is a multi-line text input, with vertical scrolling, line numbers and soft wrapping.
+///
+/// Tabs are replaced by spaces, and a new line keeps the indentation of the previous one.
+/// The lines can be colored by a highlighter (for syntax highlighting)
+pub struct TextArea {
+	rect: Rect,
+	style: TextAreaStyle,
+	lines: Vec<String>,
+	highlighter: Option<Highlighter>,
+	hovered: bool,
+	focused: bool,
+	/// Line and column (in chars) of the caret
+	caret: (usize, usize),
+	caret_last_update: f64,
+	caret_visible: bool,
+	/// Vertical scroll (in pixels)
+	scroll: i32,
+	rows: Vec<Row>,
+	/// Whether the rows have to be computed again
+	dirty: bool,
+	/// Incremented at each change of the text, so that it's not joined at each update to find the changes
+	revision: u64,
+	line_height: u32,
+	gutter_width: u32,
+}
+
+impl TextArea {
+	const PADDING: u32 = 4;
+	const SCROLLBAR_WIDTH: u32 = 6;
+	/// Number of rows scrolled by one step of the mouse wheel
+	const SCROLL_ROWS: i32 = 3;
+
+	pub fn new(rect: Rect, style: Option<TextAreaStyle>, text: &str) -> Self {
+		let mut text_area = Self {
+			rect,
+			style: style.unwrap_or_default(),
+			lines: Vec::new(),
+			highlighter: None,
+			hovered: false,
+			focused: false,
+			caret: (0, 0),
+			caret_last_update: 0.0,
+			caret_visible: true,
+			scroll: 0,
+			rows: Vec::new(),
+			dirty: true,
+			revision: 0,
+			line_height: 0,
+			gutter_width: 0,
+		};
+		text_area.set_text(text);
+		text_area
+	}
+
+	pub fn get_text(&self) -> String {
+		self.lines.join("\n")
+	}
+
+	/// Replaces the whole text, the caret goes back to the start
+	pub fn set_text(&mut self, text: &str) {
+		let tab = " ".repeat(self.style.tab_size);
+		self.lines = text.replace("\r\n", "\n").split('\n').map(|line| line.replace('\t', &tab)).collect();
+		self.caret = (0, 0);
+		self.scroll = 0;
+		// The rows of the old text can't be drawn, the new ones are computed at the next update
+		self.rows.clear();
+		self.dirty = true;
+		self.revision += 1;
+	}
+
+	pub fn set_highlighter(&mut self, highlighter: Option<Highlighter>) {
+		self.highlighter = highlighter;
+	}
+
+	pub fn line_count(&self) -> usize {
+		self.lines.len()
+	}
+
+	/// Line and column (in chars) of the caret
+	pub fn get_caret(&self) -> (usize, usize) {
+		self.caret
+	}
+
+	fn char_count(&self, line: usize) -> usize {
+		self.lines[line].chars().count()
+	}
+
+	/// Byte index of the char at 'column' in the line
+	fn byte_index(line: &str, column: usize) -> usize {
+		line.char_indices().nth(column).map_or(line.len(), |(index, _)| index)
+	}
+
+	fn slice(line: &str, start: usize, end: usize) -> &str {
+		&line[Self::byte_index(line, start)..Self::byte_index(line, end)]
+	}
+
+	/// Leading spaces of the line
+	fn indentation(line: &str) -> usize {
+		line.chars().take_while(|c| *c == ' ').count()
+	}
+
+	fn text_rect(&self) -> Rect {
+		let left = self.rect.left() + (self.gutter_width + Self::PADDING) as i32;
+		let width = self.rect.width().saturating_sub(self.gutter_width + 2 * Self::PADDING + Self::SCROLLBAR_WIDTH);
+		let height = self.rect.height().saturating_sub(2 * Self::PADDING);
+		rect!(left, self.rect.top() + Self::PADDING as i32, width.max(1), height.max(1))
+	}
+
+	fn content_height(&self) -> i32 {
+		(self.rows.len() as u32 * self.line_height) as i32
+	}
+
+	fn max_scroll(&self) -> i32 {
+		(self.content_height() - self.text_rect().height() as i32).max(0)
+	}
+
+	/// Cuts the lines in rows, and measures the line height and the gutter width
+	fn layout(&mut self, text_drawer: &TextDrawer) {
+		let text_style = &self.style.text_style;
+		self.line_height = text_drawer.text_size(text_style, "Ag").1;
+		self.gutter_width = if self.style.line_numbers {
+			let digits = self.lines.len().to_string().len();
+			text_drawer.text_size(text_style, &"9".repeat(digits)).0 + 2 * Self::PADDING
+		} else {
+			0
+		};
+
+		let width = self.text_rect().width();
+		let fits = |line: &str, start: usize, end: usize| {
+			text_drawer.text_size(text_style, Self::slice(line, start, end)).0 <= width
+		};
+		let mut rows = Vec::new();
+		for (index, line) in self.lines.iter().enumerate() {
+			let char_count = line.chars().count();
+			if !self.style.soft_wrap {
+				rows.push(Row { line: index, start: 0, end: char_count });
+				continue;
+			}
+			let mut start = 0;
+			loop {
+				if fits(line, start, char_count) {
+					rows.push(Row { line: index, start, end: char_count });
+					break;
+				}
+				// Longest part of the line that fits (at least one char)
+				let (mut low, mut high) = (start + 1, char_count);
+				while low < high {
+					let middle = (low + high).div_ceil(2);
+					if fits(line, start, middle) {
+						low = middle;
+					} else {
+						high = middle - 1;
+					}
+				}
+				// Cut after the last space if possible, to keep the words whole
+				let part = Self::slice(line, start, low);
+				let end = match part.chars().collect::<Vec<char>>().iter().rposition(|c| *c == ' ') {
+					Some(space) if space > 0 => start + space + 1,
+					_ => low,
+				};
+				rows.push(Row { line: index, start, end });
+				start = end;
+			}
+		}
+		self.rows = rows;
+		self.dirty = false;
+	}
+
+	/// Index of the row containing the caret
+	fn caret_row(&self) -> usize {
+		let (line, column) = self.caret;
+		self.rows
+			.iter()
+			.enumerate()
+			.filter(|(_, row)| row.line == line && row.start <= column)
+			.find(|(index, row)| column < row.end || self.rows.get(index + 1).is_none_or(|next| next.line != line))
+			.map_or(0, |(index, _)| index)
+	}
+
+	/// Column of the char of the row closest to 'x' (relative to the text rect)
+	fn column_at(&self, text_drawer: &TextDrawer, row: &Row, x: i32) -> usize {
+		let line = &self.lines[row.line];
+		let mut previous_width = 0;
+		for column in row.start..row.end {
+			let width =
+				text_drawer.text_size(&self.style.text_style, Self::slice(line, row.start, column + 1)).0 as i32;
+			if x < (previous_width + width) / 2 {
+				return column;
+			}
+			previous_width = width;
+		}
+		// The caret can't be after the last char of a wrapped row, it would be on the next row
+		let is_last_row = row.end == self.char_count(row.line);
+		if is_last_row || row.end == row.start {
+			row.end
+		} else {
+			row.end - 1
+		}
+	}
+
+	fn caret_x(&self, text_drawer: &TextDrawer) -> i32 {
+		let row = &self.rows[self.caret_row()];
+		let line = &self.lines[row.line];
+		text_drawer.text_size(&self.style.text_style, Self::slice(line, row.start, self.caret.1)).0 as i32
+	}
+
+	fn scroll_to_caret(&mut self) {
+		let caret_top = (self.caret_row() as u32 * self.line_height) as i32;
+		let visible_height = self.text_rect().height() as i32;
+		if caret_top < self.scroll {
+			self.scroll = caret_top;
+		} else if caret_top + self.line_height as i32 > self.scroll + visible_height {
+			self.scroll = caret_top + self.line_height as i32 - visible_height;
+		}
+	}
+
+	fn insert_text(&mut self, text: &str) {
+		if text.is_empty() {
+			return;
+		}
+		let tab = " ".repeat(self.style.tab_size);
+		let text = text.replace("\r\n", "\n").replace('\t', &tab);
+		let (line, column) = self.caret;
+		let index = Self::byte_index(&self.lines[line], column);
+		let end = self.lines[line].split_off(index);
+
+		let mut new_lines = text.split('\n');
+		self.lines[line].push_str(new_lines.next().unwrap_or_default());
+		let mut caret = (line, self.char_count(line));
+		for new_line in new_lines {
+			caret = (caret.0 + 1, new_line.chars().count());
+			self.lines.insert(caret.0, new_line.to_string());
+		}
+		self.lines[caret.0].push_str(&end);
+		self.caret = caret;
+		self.dirty = true;
+		self.revision += 1;
+	}
+
+	/// Starts a new line, with the indentation of the current one (plus one level after an opening bracket)
+	fn new_line(&mut self) {
+		let (line, column) = self.caret;
+		let before_caret = Self::slice(&self.lines[line], 0, column);
+		let mut indentation = Self::indentation(before_caret).min(column);
+		if before_caret.trim_end().ends_with(['{', '(', '[']) {
+			indentation += self.style.tab_size;
+		}
+		self.insert_text(&format!("\n{}", " ".repeat(indentation)));
+	}
+
+	fn backspace(&mut self) {
+		let (line, column) = self.caret;
+		if column == 0 {
+			if line > 0 {
+				// Joins the line with the previous one
+				let removed_line = self.lines.remove(line);
+				self.caret = (line - 1, self.char_count(line - 1));
+				self.lines[line - 1].push_str(&removed_line);
+				self.revision += 1;
+			}
+		} else {
+			// In the indentation, a whole indentation level is removed
+			let tab_size = self.style.tab_size;
+			let start = if Self::indentation(&self.lines[line]) >= column {
+				(column - 1) / tab_size * tab_size
+			} else {
+				column - 1
+			};
+			let text = &mut self.lines[line];
+			text.replace_range(Self::byte_index(text, start)..Self::byte_index(text, column), "");
+			self.caret.1 = start;
+			self.revision += 1;
+		}
+		self.dirty = true;
+	}
+
+	fn unindent(&mut self) {
+		let (line, column) = self.caret;
+		let removed = Self::indentation(&self.lines[line]).min(self.style.tab_size);
+		if removed == 0 {
+			return;
+		}
+		self.lines[line].replace_range(..removed, "");
+		self.caret.1 = column.saturating_sub(removed);
+		self.dirty = true;
+		self.revision += 1;
+	}
+
+	/// Moves the caret to the row above (-1) or below (1), keeping its horizontal position
+	fn move_vertically(&mut self, text_drawer: &TextDrawer, direction: i32) {
+		let target_row = self.caret_row() as i32 + direction;
+		if target_row < 0 {
+			self.caret.1 = 0;
+		} else if target_row as usize >= self.rows.len() {
+			self.caret.1 = self.char_count(self.caret.0);
+		} else {
+			let x = self.caret_x(text_drawer);
+			let row = &self.rows[target_row as usize];
+			self.caret = (row.line, self.column_at(text_drawer, row, x));
+		}
+	}
+
+	fn draw_row(&self, canvas: &mut Canvas<Window>, text_drawer: &TextDrawer, row: &Row, colors: &[Color], y: i32) {
+		let line = &self.lines[row.line];
+		let text_style = &self.style.text_style;
+		let left = self.text_rect().left();
+
+		// The row is drawn by parts of the same color
+		let mut start = row.start;
+		while start < row.end {
+			let end = (start..row.end).find(|column| colors[*column] != colors[start]).unwrap_or(row.end);
+			let part = Self::slice(line, start, end);
+			if !part.trim().is_empty() {
+				let x = text_drawer.text_size(text_style, Self::slice(line, row.start, start)).0 as i32;
+				let part_style = TextStyle {
+					color: colors[start],
+					font_size: text_style.font_size,
+					font_name: text_style.font_name.clone(),
+					font_style: text_style.font_style,
+				};
+				text_drawer.draw(canvas, point!(left + x, y), &part_style, part, Align::TopLeft);
+			}
+			start = end;
+		}
+	}
+}
+
+impl Widget for TextArea {
+	fn update(&mut self, input: &Input, delta: f64, text_drawer: &mut TextDrawer) -> bool {
+		let mut changed = false;
+		if self.dirty {
+			self.layout(text_drawer);
+			changed = true;
+		}
+
+		// Caret blinking
+		self.caret_last_update += delta;
+		if self.caret_last_update > 0.5 {
+			self.caret_last_update = 0.0;
+			self.caret_visible = !self.caret_visible;
+			changed = true;
+		}
+
+		// Mouse
+		let mouse_position = Point::new(input.mouse.position.x, input.mouse.position.y);
+		let hovered = self.rect.contains_point(mouse_position);
+		if hovered != self.hovered {
+			self.hovered = hovered;
+			changed = true;
+		}
+		if input.mouse.left_button.is_pressed() {
+			self.focused = hovered;
+			if hovered && !self.rows.is_empty() {
+				let text_rect = self.text_rect();
+				let row_index = (mouse_position.y - text_rect.top() + self.scroll) / self.line_height.max(1) as i32;
+				let row = &self.rows[(row_index.max(0) as usize).min(self.rows.len() - 1)];
+				self.caret = (row.line, self.column_at(text_drawer, row, mouse_position.x - text_rect.left()));
+			}
+			changed = true;
+		}
+		if hovered && input.mouse.wheel.y != 0 {
+			self.scroll -= input.mouse.wheel.y * Self::SCROLL_ROWS * self.line_height as i32;
+			self.scroll = self.scroll.clamp(0, self.max_scroll());
+			changed = true;
+		}
+
+		if !self.focused {
+			return changed;
+		}
+
+		// Keyboard
+		let caret = self.caret;
+		let keys_state = &input.keys_state;
+		let shift = keys_state.lshift.is_down() || keys_state.rshift.is_down();
+		if input.binding_pressed("paste") && input.clipboard.has_clipboard_text() {
+			self.insert_text(&input.clipboard.clipboard_text().unwrap());
+		} else if !input.typed_text.is_empty() {
+			self.insert_text(&input.typed_text);
+		}
+		if keys_state.enter.is_pressed() {
+			self.new_line();
+		}
+		// Ctrl+Tab moves the focus to the next widget
+		let ctrl = keys_state.lctrl.is_down() || keys_state.rctrl.is_down();
+		if keys_state.tab.is_pressed() && !ctrl {
+			if shift {
+				self.unindent();
+			} else {
+				let spaces = self.style.tab_size - self.caret.1 % self.style.tab_size;
+				self.insert_text(&" ".repeat(spaces));
+			}
+		}
+		if keys_state.backspace.is_pressed() {
+			self.backspace();
+		}
+		let edited = self.dirty;
+		if edited {
+			self.layout(text_drawer);
+		}
+
+		// Caret movement
+		if keys_state.left.is_pressed() {
+			if self.caret.1 > 0 {
+				self.caret.1 -= 1;
+			} else if self.caret.0 > 0 {
+				self.caret = (self.caret.0 - 1, self.char_count(self.caret.0 - 1));
+			}
+		}
+		if keys_state.right.is_pressed() {
+			if self.caret.1 < self.char_count(self.caret.0) {
+				self.caret.1 += 1;
+			} else if self.caret.0 + 1 < self.lines.len() {
+				self.caret = (self.caret.0 + 1, 0);
+			}
+		}
+		if keys_state.up.is_pressed() {
+			self.move_vertically(text_drawer, -1);
+		}
+		if keys_state.down.is_pressed() {
+			self.move_vertically(text_drawer, 1);
+		}
+
+		if self.caret != caret || edited {
+			self.scroll_to_caret();
+			self.scroll = self.scroll.clamp(0, self.max_scroll());
+			self.caret_visible = true;
+			self.caret_last_update = 0.0;
+			changed = true;
+		}
+
+		changed
+	}
+
+	fn draw(&self, canvas: &mut Canvas<Window>, text_drawer: &TextDrawer) {
+		let style = &self.style;
+		fill_rect(canvas, self.rect, style.background_color);
+		if self.gutter_width > 0 {
+			fill_rect(
+				canvas,
+				rect!(self.rect.left(), self.rect.top(), self.gutter_width, self.rect.height()),
+				style.gutter_color,
+			);
+		}
+
+		let text_rect = self.text_rect();
+		canvas.set_clip_rect(rect!(
+			self.rect.left() + 1,
+			self.rect.top() + 1,
+			self.rect.width().saturating_sub(2).max(1),
+			self.rect.height().saturating_sub(2).max(1)
+		));
+
+		let first_row = (self.scroll / self.line_height.max(1) as i32) as usize;
+		let visible_rows = text_rect.height() / self.line_height.max(1) + 2;
+		let caret_row = self.caret_row();
+		let mut colors = Vec::new();
+		for (index, row) in self.rows.iter().enumerate().skip(first_row).take(visible_rows as usize) {
+			let y = text_rect.top() + (index as u32 * self.line_height) as i32 - self.scroll;
+
+			// Line number, only on the first row of a line
+			if row.start == 0 && style.line_numbers {
+				let line_number_style = TextStyle {
+					color: style.line_number_color,
+					font_size: style.text_style.font_size,
+					font_name: style.text_style.font_name.clone(),
+					font_style: style.text_style.font_style,
+				};
+				let position = point!(self.rect.left() + (self.gutter_width - Self::PADDING) as i32, y);
+				text_drawer.draw(canvas, position, &line_number_style, &(row.line + 1).to_string(), Align::TopRight);
+			}
+			// Colors of the chars of the line
+			if row.start == 0 || index == first_row {
+				colors = vec![style.text_style.color; self.char_count(row.line)];
+				if let Some(highlighter) = &self.highlighter {
+					for (range, color) in highlighter(&self.lines[row.line]) {
+						colors.iter_mut().take(range.end).skip(range.start).for_each(|c| *c = color);
+					}
+				}
+			}
+			self.draw_row(canvas, text_drawer, row, &colors, y);
+
+			// Caret
+			if self.focused && self.caret_visible && index == caret_row {
+				let caret_x = self.caret_x(text_drawer);
+				fill_rect(canvas, rect!(text_rect.left() + caret_x, y, 1, self.line_height), Colors::BLACK);
+			}
+		}
+		canvas.set_clip_rect(None);
+
+		// Scrollbar
+		let content_height = self.content_height();
+		if content_height > text_rect.height() as i32 {
+			let height = self.rect.height() as i32 * text_rect.height() as i32 / content_height;
+			let y = self.rect.top() + self.rect.height() as i32 * self.scroll / content_height;
+			let scrollbar_rect =
+				rect!(self.rect.right() - Self::SCROLLBAR_WIDTH as i32, y, Self::SCROLLBAR_WIDTH, height);
+			fill_rect(canvas, scrollbar_rect, style.scrollbar_color);
+		}
+
+		draw_rect(canvas, self.rect, if self.focused { style.contour_focused_color } else { style.contour_color });
+	}
+
+	fn is_hovered(&self) -> bool {
+		self.hovered
+	}
+	fn is_focusable(&self) -> bool {
+		true
+	}
+
+	fn is_focused(&self) -> bool {
+		self.focused
+	}
+
+	fn set_focused(&mut self, focused: bool) {
+		self.focused = focused;
+	}
+
+	fn captures_tab(&self) -> bool {
+		true
+	}
+
+	fn get_rect(&self) -> Option<Rect> {
+		Some(self.rect)
+	}
+
+	fn access_node(&self) -> Option<AccessNode> {
+		Some(AccessNode { value: Some(self.get_text()), ..AccessNode::new(Role::TextArea, "") })
+	}
+
+	fn get_cursor(&self) -> Option<MouseCursor> {
+		self.hovered.then_some(MouseCursor::IBeam)
+	}
+
+	fn reset_interaction(&mut self) {
+		self.hovered = false;
+	}
+
+	fn get_widget_value(&self) -> Option<WidgetValue> {
+		Some(WidgetValue::Text(self.get_text()))
+	}
+
+	fn get_value_revision(&self) -> Option<u64> {
+		Some(self.revision)
+	}
+}
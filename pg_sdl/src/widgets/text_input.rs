@@ -1,9 +1,58 @@
 use crate::canvas::{draw_rect, draw_rounded_rect, fill_rect, fill_rounded_rect};
-use crate::input::{KeyState, KeysState, Shortcut};
+use crate::input::{Composition, KeyState, KeysState, MouseCursor};
 use crate::prelude::*;
-use crate::widgets::{HOVER, PUSH};
+use crate::widgets::binding::BoundValue;
+use crate::widgets::{AccessNode, Binding, Role, WidgetEvent, WidgetValue, HOVER, PUSH};
+use regex::Regex;
 use sdl2::keyboard::Keycode;
 use sdl2::render::BlendMode;
+use std::any::Any;
+use unicode_segmentation::UnicodeSegmentation;
+
+/// A validator accepts or rejects the content of a text input.
+///
+/// It is called with the whole content after each edit, and the edits it rejects are cancelled.
+/// The partial texts typed on the way to a valid one (like "-" for a negative integer) must be accepted
+pub struct Validator(Box<dyn Fn(&str) -> bool>);
+
+impl Validator {
+	pub fn new(validate: impl Fn(&str) -> bool + 'static) -> Self {
+		Self(Box::new(validate))
+	}
+
+	/// Integers, with an optional minus sign
+	pub fn integer() -> Self {
+		Self::new(|text| text.strip_prefix('-').unwrap_or(text).chars().all(|c| c.is_ascii_digit()))
+	}
+
+	/// Decimal numbers, with an optional minus sign and at most one decimal point
+	pub fn float() -> Self {
+		Self::new(|text| {
+			let digits = text.strip_prefix('-').unwrap_or(text);
+			digits.chars().all(|c| c.is_ascii_digit() || c == '.') && digits.matches('.').count() <= 1
+		})
+	}
+
+	/// Texts of at most 'length' characters (graphemes)
+	pub fn max_length(length: usize) -> Self {
+		Self::new(move |text| text.graphemes(true).count() <= length)
+	}
+
+	/// Texts matching the whole pattern
+	pub fn regex(pattern: &str) -> Result<Self, regex::Error> {
+		let regex = Regex::new(&format!("^(?:{})$", pattern))?;
+		Ok(Self::new(move |text| regex.is_match(text)))
+	}
+
+	/// Texts accepted by both validators
+	pub fn and(self, other: Validator) -> Self {
+		Self::new(move |text| self.is_valid(text) && other.is_valid(text))
+	}
+
+	pub fn is_valid(&self, text: &str) -> bool {
+		(self.0)(text)
+	}
+}
 
 pub struct TextInputStyle {
 	background_color: Color,
@@ -11,6 +60,7 @@ pub struct TextInputStyle {
 	background_pushed_color: Color,
 	contour_color: Color,
 	contour_focused_color: Color,
+	placeholder_color: Color,
 	corner_radius: Option<u16>,
 	text_style: TextStyle,
 }
@@ -23,6 +73,7 @@ impl Default for TextInputStyle {
 			background_pushed_color: darker(Colors::WHITE, PUSH),
 			contour_color: Colors::BLACK,
 			contour_focused_color: paler(Colors::BLUE, 0.9),
+			placeholder_color: Colors::GREY,
 			corner_radius: Some(4),
 			text_style: TextStyle::default(),
 		}
@@ -36,11 +87,29 @@ pub struct TextInput {
 	hovered: bool,
 	is_focused: bool,
 	carrot_last_update: f64,
+	/// Byte index of the carrot in the content, always on a grapheme boundary
 	carrot_position: usize,
 	carrot_visible: bool,
 	selection: Option<(usize, usize)>,
 	is_selecting: bool,
+	/// Horizontal scroll of the text (in pixels), when it's wider than the text input
+	scroll: f64,
+	/// Text being composed with an input method, drawn at the carrot position
+	composition: Option<Composition>,
+	/// Contents and carrot positions before the last edits
+	undo_stack: Vec<(String, usize)>,
+	redo_stack: Vec<(String, usize)>,
+	/// Whether the last edit was typing, consecutive typed chars are undone together
+	typing: bool,
+	validator: Option<Validator>,
+	/// Text shown when the content is empty
+	placeholder: Option<String>,
+	/// If true, the content is drawn as bullets and can't be copied
+	password: bool,
+	/// Whether Enter was pressed this frame
+	submitted: bool,
 	pub state: KeyState,
+	binding: Option<BoundValue<String>>,
 }
 
 impl TextInput {
@@ -61,30 +130,326 @@ impl TextInput {
 			carrot_visible: true,
 			selection: None,
 			is_selecting: false,
+			scroll: 0.0,
+			composition: None,
+			undo_stack: Vec::new(),
+			redo_stack: Vec::new(),
+			typing: false,
+			validator: None,
+			placeholder: None,
+			password: false,
+			submitted: false,
+			binding: None,
 		}
 	}
 	const LEFT_SHIFT: i32 = 5;
+	const HISTORY_SIZE: usize = 100;
+	/// Speed of the scrolling when a selection is dragged beyond the edges (in pixels per second)
+	const AUTO_SCROLL_SPEED: f64 = 400.0;
+
+	/// Replaces the content, the undo history is cleared
+	pub fn set_content(&mut self, content: String) {
+		self.carrot_position = content.len();
+		self.selection = None;
+		self.content = content;
+		self.scroll = 0.0;
+		self.undo_stack.clear();
+		self.redo_stack.clear();
+		self.typing = false;
+	}
+
+	/// Binds the content to the app, the text input takes the content of the binding first
+	/// (the content set by the app replaces the current one like `set_content`)
+	pub fn set_binding(&mut self, binding: Option<Binding<String>>) {
+		self.binding = binding.map(BoundValue::new);
+	}
+
+	/// The edits rejected by the validator are cancelled, the current content is not checked
+	pub fn set_validator(&mut self, validator: Option<Validator>) {
+		self.validator = validator;
+	}
+
+	pub fn set_placeholder(&mut self, placeholder: Option<&str>) {
+		self.placeholder = placeholder.map(|placeholder| placeholder.to_string());
+	}
+
+	pub fn set_password(&mut self, password: bool) {
+		self.password = password;
+	}
 
+	/// Whether Enter was pressed in the text input this frame
+	pub fn is_submitted(&self) -> bool {
+		self.submitted
+	}
+
+	/// Byte index of the grapheme boundary before 'index'
+	fn previous_boundary(&self, index: usize) -> usize {
+		self.content[..index].grapheme_indices(true).next_back().map_or(0, |(i, _)| i)
+	}
+
+	/// Byte index of the grapheme boundary after 'index'
+	fn next_boundary(&self, index: usize) -> usize {
+		self.content[index..].graphemes(true).next().map_or(index, |grapheme| index + grapheme.len())
+	}
+
+	/// Grapheme boundary closest to the mouse, None if the mouse is after the text
 	fn get_carrot_position_from_mouse(&self, text_drawer: &mut TextDrawer, mouse_x: i32) -> Option<usize> {
-		let mut x: u32 = 0;
-		for (i, c) in self.content.chars().enumerate() {
-			let text_width = text_drawer.text_size(&self.style.text_style, &c.to_string()).0;
-			x += text_width;
-			if x >= mouse_x as u32 {
-				return Some(i);
+		let mut previous_width = 0;
+		for (index, grapheme) in self.content.grapheme_indices(true) {
+			let width = self.text_width(text_drawer, &self.content[..index + grapheme.len()]);
+			if mouse_x - Self::LEFT_SHIFT < (previous_width + width) / 2 {
+				return Some(index);
 			}
+			previous_width = width;
+		}
+		None
+	}
+
+	/// Byte index of the start of the word before 'index'
+	fn previous_word(&self, index: usize) -> usize {
+		let mut words = self.content[..index].split_word_bound_indices().rev();
+		words.find(|(_, word)| !word.trim().is_empty()).map_or(0, |(i, _)| i)
+	}
+
+	/// Byte index of the end of the word after 'index'
+	fn next_word(&self, index: usize) -> usize {
+		let mut words = self.content[index..].split_word_bound_indices();
+		words.find(|(_, word)| !word.trim().is_empty()).map_or(self.content.len(), |(i, word)| index + i + word.len())
+	}
+
+	/// Byte range of the word (or of the spaces) at 'index'
+	fn word_at(&self, index: usize) -> (usize, usize) {
+		let mut words = self.content.split_word_bound_indices();
+		let word = words
+			.find(|(i, word)| index < i + word.len())
+			.or_else(|| self.content.split_word_bound_indices().next_back());
+		word.map_or((0, 0), |(i, word)| (i, i + word.len()))
+	}
+
+	/// Moves the carrot, extending the selection from its other end when 'selecting'
+	fn move_carrot(&mut self, position: usize, selecting: bool) {
+		if selecting {
+			let anchor = match self.selection {
+				Some((start, end)) if self.carrot_position == start => end,
+				Some((start, _)) => start,
+				None => self.carrot_position,
+			};
+			self.selection = if anchor == position { None } else { Some((anchor.min(position), anchor.max(position))) };
+		} else {
+			self.selection = None;
+		}
+		self.carrot_position = position;
+		self.typing = false;
+	}
+
+	fn select(&mut self, start: usize, end: usize) {
+		self.selection = if start == end { None } else { Some((start, end)) };
+		self.carrot_position = end;
+	}
+
+	/// Saves the content before an edit, so that it can be undone
+	fn save_history(&mut self, typing: bool) {
+		if !(typing && self.typing) {
+			self.undo_stack.push((self.content.clone(), self.carrot_position));
+			if self.undo_stack.len() > Self::HISTORY_SIZE {
+				self.undo_stack.remove(0);
+			}
+		}
+		self.redo_stack.clear();
+		self.typing = typing;
+	}
+
+	fn undo(&mut self) {
+		if let Some((content, carrot_position)) = self.undo_stack.pop() {
+			self.redo_stack.push((std::mem::replace(&mut self.content, content), self.carrot_position));
+			self.carrot_position = carrot_position;
+			self.selection = None;
+			self.typing = false;
+		}
+	}
+
+	fn redo(&mut self) {
+		if let Some((content, carrot_position)) = self.redo_stack.pop() {
+			self.undo_stack.push((std::mem::replace(&mut self.content, content), self.carrot_position));
+			self.carrot_position = carrot_position;
+			self.selection = None;
+			self.typing = false;
+		}
+	}
+
+	/// Replaces the selected text (or inserts at the carrot) by 'text', if the validator accepts the result
+	fn insert(&mut self, text: &str, typing: bool) -> bool {
+		let (start, end) = self.selection.unwrap_or((self.carrot_position, self.carrot_position));
+		let mut content = self.content.clone();
+		content.replace_range(start..end, text);
+		if self.validator.as_ref().is_some_and(|validator| !validator.is_valid(&content)) {
+			return false;
+		}
+		self.save_history(typing && self.selection.is_none());
+		self.content = content;
+		self.carrot_position = start + text.len();
+		self.selection = None;
+		true
+	}
+
+	/// Removes the selected text, or the text between the carrot and 'position'
+	fn delete_to(&mut self, position: usize) {
+		if self.selection.is_some() {
+			self.insert("", false);
+		} else if position != self.carrot_position {
+			self.selection = Some((self.carrot_position.min(position), self.carrot_position.max(position)));
+			if !self.insert("", false) {
+				self.selection = None;
+			}
+		}
+	}
+
+	/// Text as drawn, with bullets instead of the chars in password mode
+	fn displayed(&self, text: &str) -> String {
+		if self.password {
+			"•".repeat(text.graphemes(true).count())
+		} else {
+			text.to_string()
+		}
+	}
+
+	fn text_width(&self, text_drawer: &TextDrawer, text: &str) -> i32 {
+		text_drawer.text_size(&self.style.text_style, &self.displayed(text)).0 as i32
+	}
+
+	fn visible_width(&self) -> i32 {
+		self.rect.width() as i32 - 2 * Self::LEFT_SHIFT
+	}
+
+	fn max_scroll(&self, text_drawer: &TextDrawer) -> f64 {
+		(self.text_width(text_drawer, &self.content) - self.visible_width()).max(0) as f64
+	}
+
+	/// Scrolls the text just enough for the carrot to be visible
+	fn scroll_to_carrot(&mut self, text_drawer: &TextDrawer) {
+		let carrot_x = self.text_width(text_drawer, &self.content[..self.carrot_position]) as f64;
+		if carrot_x < self.scroll {
+			self.scroll = carrot_x;
+		} else if carrot_x > self.scroll + self.visible_width() as f64 {
+			self.scroll = carrot_x - self.visible_width() as f64;
+		}
+		self.scroll = self.scroll.clamp(0.0, self.max_scroll(text_drawer));
+	}
+
+	/// Edits and carrot movements from the keyboard
+	fn update_keyboard(&mut self, input: &Input) -> bool {
+		let mut changed = false;
+		let keys_state = &input.keys_state;
+		let ctrl = keys_state.lctrl.is_down() || keys_state.rctrl.is_down();
+		let shift = keys_state.lshift.is_down() || keys_state.rshift.is_down();
+
+		// History
+		if input.binding_pressed("redo") {
+			self.redo();
+			return true;
+		}
+		if input.binding_pressed("undo") {
+			self.undo();
+			return true;
+		}
+		if input.binding_pressed("select all") {
+			self.select(0, self.content.len());
+			return true;
+		}
+
+		// Clipboard
+		if input.binding_pressed("paste") && input.clipboard.has_clipboard_text() {
+			self.insert(&input.clipboard.clipboard_text().unwrap(), false);
+			return true;
+		}
+		// The content of a password can't be copied
+		if input.binding_pressed("copy") && !self.password {
+			if self.selection.is_some() {
+				let (start, end) = self.selection.unwrap();
+				let text = self.content[start..end].to_string();
+				input.clipboard.set_clipboard_text(&text).unwrap();
+				return true;
+			}
+			input.clipboard.set_clipboard_text(&self.content).unwrap();
+			return true;
+		}
+		if input.binding_pressed("cut") && !self.password {
+			if self.selection.is_none() {
+				self.selection = Some((0, self.content.len()));
+			}
+			let (start, end) = self.selection.unwrap();
+			let text = self.content[start..end].to_string();
+			if self.insert("", false) {
+				input.clipboard.set_clipboard_text(&text).unwrap();
+			}
+			return true;
+		}
+
+		// Text input
+		if !input.typed_text.is_empty() {
+			self.insert(&input.typed_text, true);
+			changed = true;
+		}
+		if keys_state.enter.is_pressed() {
+			self.submitted = true;
+			changed = true;
+		}
+		if keys_state.backspace.is_pressed() {
+			let position = if ctrl {
+				self.previous_word(self.carrot_position)
+			} else {
+				self.previous_boundary(self.carrot_position)
+			};
+			self.delete_to(position);
+			changed = true;
+		}
+		if keys_state.delete.is_pressed() {
+			let position =
+				if ctrl { self.next_word(self.carrot_position) } else { self.next_boundary(self.carrot_position) };
+			self.delete_to(position);
+			changed = true;
+		}
+
+		// Carrot movement
+		if keys_state.left.is_pressed() {
+			let position = match self.selection {
+				Some((start, _)) if !shift && !ctrl => start,
+				_ if ctrl => self.previous_word(self.carrot_position),
+				_ => self.previous_boundary(self.carrot_position),
+			};
+			self.move_carrot(position, shift);
+			changed = true;
+		}
+		if keys_state.right.is_pressed() {
+			let position = match self.selection {
+				Some((_, end)) if !shift && !ctrl => end,
+				_ if ctrl => self.next_word(self.carrot_position),
+				_ => self.next_boundary(self.carrot_position),
+			};
+			self.move_carrot(position, shift);
+			changed = true;
+		}
+		if keys_state.home.is_pressed() {
+			self.move_carrot(0, shift);
+			changed = true;
+		}
+		if keys_state.end.is_pressed() {
+			self.move_carrot(self.content.len(), shift);
+			changed = true;
 		}
-		return None;
+
+		changed
 	}
 }
 
 impl Widget for TextInput {
-	fn update(&mut self, input: &Input, _delta: f64, text_drawer: &mut TextDrawer) -> bool {
+	fn update(&mut self, input: &Input, delta: f64, text_drawer: &mut TextDrawer) -> bool {
 		let mut changed = false;
 		self.state.update();
+		self.submitted = false;
 
 		// Carrot blinking
-		self.carrot_last_update += _delta;
+		self.carrot_last_update += delta;
 		if self.carrot_last_update > 0.5 {
 			self.carrot_last_update = 0.0;
 			self.carrot_visible = !self.carrot_visible;
@@ -99,44 +464,45 @@ impl Widget for TextInput {
 			changed = true;
 		}
 		if hovered {
-			// Mouse click
-			if input.mouse.left_button_double_clicked() {
-				self.selection = Some((0, self.content.len()));
-				changed = true;
-			} else if input.mouse.left_button.is_pressed() {
-				self.selection = None;
-
-				// Carrot position
-				let mouse_x = input.mouse.position.x - self.rect.x;
-				self.carrot_position =
-					if let Some(new_carrot_position) = self.get_carrot_position_from_mouse(text_drawer, mouse_x) {
-						new_carrot_position
-					} else {
-						self.content.len()
-					};
+			let mouse_x = input.mouse.position.x - self.rect.x + self.scroll as i32;
+			let mouse_carrot_position =
+				self.get_carrot_position_from_mouse(text_drawer, mouse_x).unwrap_or(self.content.len());
 
-				// Selection
+			// Mouse click
+			if input.mouse.left_button.is_pressed() {
+				match input.mouse.left_button.get_click_count() {
+					1 => {
+						let shift = input.keys_state.lshift.is_down() || input.keys_state.rshift.is_down();
+						self.move_carrot(mouse_carrot_position, shift && self.is_focused);
+						self.is_selecting = true;
+					}
+					2 => {
+						let (start, end) = self.word_at(mouse_carrot_position);
+						self.select(start, end);
+					}
+					_ => self.select(0, self.content.len()),
+				}
 				self.state.press();
 				self.is_focused = true;
-				self.is_selecting = true;
 				changed = true;
-			} else if input.mouse.left_button.is_down() && self.is_selecting {
-				// Selection
-				let mouse_x = input.mouse.position.x - self.rect.x;
-				let new_carrot_position =
-					if let Some(new_carrot_position) = self.get_carrot_position_from_mouse(text_drawer, mouse_x) {
-						new_carrot_position
-					} else {
-						self.content.len()
-					};
-				if new_carrot_position != self.carrot_position {
-					if self.carrot_position > new_carrot_position {
-						self.selection = Some((new_carrot_position, self.carrot_position));
-					} else {
-						self.selection = Some((self.carrot_position, new_carrot_position));
-					}
-					changed = true;
-				}
+			}
+		}
+
+		// Selection by dragging, the text scrolls when the mouse is beyond the edges
+		if input.mouse.left_button.is_down() && self.is_selecting {
+			let (left, right) = (self.rect.left() + Self::LEFT_SHIFT, self.rect.right() - Self::LEFT_SHIFT);
+			let mouse_x = input.mouse.position.x;
+			if mouse_x < left || mouse_x > right {
+				let direction = if mouse_x < left { -1.0 } else { 1.0 };
+				self.scroll += direction * Self::AUTO_SCROLL_SPEED * delta;
+				self.scroll = self.scroll.clamp(0.0, self.max_scroll(text_drawer));
+				changed = true;
+			}
+			let x = mouse_x.clamp(left, right) - self.rect.x + self.scroll as i32;
+			let position = self.get_carrot_position_from_mouse(text_drawer, x).unwrap_or(self.content.len());
+			if position != self.carrot_position {
+				self.move_carrot(position, true);
+				changed = true;
 			}
 		}
 
@@ -155,84 +521,28 @@ impl Widget for TextInput {
 			changed = true;
 		}
 
+		// Input method composition
+		let composition = if self.is_focused { input.composition.clone() } else { None };
+		if composition != self.composition {
+			self.composition = composition;
+			changed = true;
+		}
+
 		// Keyboard input
 		if self.is_focused {
-			// Clipboard
-			if input.shortcut_pressed(&Shortcut::PASTE()) && input.clipboard.has_clipboard_text() {
-				if self.selection.is_some() {
-					let (start, end) = self.selection.unwrap();
-					self.content.drain(start..end);
-					self.carrot_position = start;
-					self.selection = None;
-				}
-				let clipboard_text = input.clipboard.clipboard_text().unwrap();
-				self.content.insert_str(self.carrot_position, &clipboard_text);
-				self.carrot_position = self.carrot_position + clipboard_text.len();
-				return true;
-			}
-			if input.shortcut_pressed(&Shortcut::COPY()) {
-				if self.selection.is_some() {
-					let (start, end) = self.selection.unwrap();
-					let text = self.content[start..end].to_string();
-					input.clipboard.set_clipboard_text(&text).unwrap();
-					return true;
-				}
-				input.clipboard.set_clipboard_text(&self.content).unwrap();
-				return true;
-			}
-			if input.shortcut_pressed(&Shortcut::CUT()) {
-				if self.selection.is_some() {
-					let (start, end) = self.selection.unwrap();
-					let text = self.content.drain(start..end).collect::<String>();
-					input.clipboard.set_clipboard_text(&text).unwrap();
-					self.carrot_position = start;
-					self.selection = None;
-					return true;
-				}
-				input.clipboard.set_clipboard_text(&self.content).unwrap();
-				self.content.clear();
-				self.carrot_position = 0;
-				return true;
+			// The keys are used by the input method while composing
+			if self.composition.is_some() {
+				return changed;
 			}
 
-			// Text input
-			if let Some(c) = input.last_char {
-				if let Some((start, end)) = self.selection {
-					self.content.drain(start..end);
-					self.carrot_position = start;
-					self.selection = None;
-				}
-				changed = true;
-				self.content.insert(self.carrot_position, c);
-				if self.carrot_position < self.content.len() {
-					self.carrot_position += 1;
-				}
-			}
-			if input.keys_state.backspace.is_pressed() {
-				if self.selection.is_some() {
-					let (start, end) = self.selection.unwrap();
-					self.content.drain(start..end);
-					self.carrot_position = start;
-					self.selection = None;
-				} else if self.carrot_position > 0 {
-					self.content.remove(self.carrot_position - 1);
-					self.carrot_position -= 1;
-				}
-				changed = true;
-			}
+			changed |= self.update_keyboard(input);
 
-			// Carrot movement
-			if input.keys_state.left.is_pressed() {
-				changed = true;
-				if self.carrot_position > 0 {
-					self.carrot_position -= 1;
-				}
-			}
-			if input.keys_state.right.is_pressed() {
-				changed = true;
-				if self.carrot_position < self.content.len() {
-					self.carrot_position += 1;
-				}
+			// The text scrolls to show the carrot, and the candidate window of the input method follows it
+			if changed {
+				self.scroll_to_carrot(text_drawer);
+				let left = self.rect.left() + Self::LEFT_SHIFT - self.scroll as i32
+					+ self.text_width(text_drawer, &self.content[..self.carrot_position]);
+				input.set_text_input_rect(rect!(left, self.rect.top(), 1, self.rect.height()));
 			}
 		}
 
@@ -261,37 +571,58 @@ impl Widget for TextInput {
 			}
 		}
 
-		// Text
-		if !self.content.is_empty() {
-			text_drawer.draw(
-				canvas,
-				point!(self.rect.left() + Self::LEFT_SHIFT, self.rect.height() as i32 / 2 + self.rect.top()),
-				&self.style.text_style,
-				&self.content,
-				Align::Left,
-			);
+		// The text is clipped to the text input
+		let inside_rect =
+			rect!(self.rect.left() + 1, self.rect.top() + 1, self.rect.width() - 2, self.rect.height() - 2);
+		canvas.set_clip_rect(inside_rect);
+		let left = self.rect.left() + Self::LEFT_SHIFT - self.scroll as i32;
+
+		// Text, with the composed text inserted at the carrot position
+		let text_position = point!(left, self.rect.height() as i32 / 2 + self.rect.top());
+		let (before, after) = self.content.split_at(self.carrot_position);
+		let composition_text = self.composition.as_ref().map_or("", |composition| composition.text.as_str());
+		let text = format!("{}{}{}", self.displayed(before), composition_text, self.displayed(after));
+		if !text.is_empty() {
+			text_drawer.draw(canvas, text_position, &self.style.text_style, &text, Align::Left);
+		} else if let Some(placeholder) = self.placeholder.as_ref().filter(|placeholder| !placeholder.is_empty()) {
+			let placeholder_style = TextStyle {
+				color: self.style.placeholder_color,
+				font_size: self.style.text_style.font_size,
+				font_name: self.style.text_style.font_name.clone(),
+				font_style: self.style.text_style.font_style,
+			};
+			text_drawer.draw(canvas, text_position, &placeholder_style, placeholder, Align::Left);
+		}
+
+		// Composition underline
+		let before_width = self.text_width(text_drawer, before);
+		if let Some(composition) = &self.composition {
+			let (width, height) = text_drawer.text_size(&self.style.text_style, &composition.text);
+			let y = self.rect.top() + (self.rect.height() + height) as i32 / 2 - 2;
+			fill_rect(canvas, rect!(left + before_width, y, width.max(1), 1), self.style.text_style.color);
 		}
 
 		// Carrot
 		if self.is_focused && self.carrot_visible {
-			let carrot_x_position = if self.carrot_position != 0 {
-				text_drawer.text_size(&self.style.text_style, &self.content[..self.carrot_position]).0 as i32
-			} else {
-				0
+			let carrot_x_position = match &self.composition {
+				Some(composition) => {
+					let cursor = composition.text.char_indices().nth(composition.cursor);
+					let cursor = cursor.map_or(composition.text.len(), |(index, _)| index);
+					before_width + text_drawer.text_size(&self.style.text_style, &composition.text[..cursor]).0 as i32
+				}
+				None => before_width,
 			};
 
-			let carrot_rect =
-				Rect::new(self.rect.left() + 5 + carrot_x_position, self.rect.top() + 5, 1, self.rect.height() - 10);
+			let carrot_rect = Rect::new(left + carrot_x_position, self.rect.top() + 5, 1, self.rect.height() - 10);
 			fill_rect(canvas, carrot_rect, Colors::BLACK);
 		}
 
 		// Selection
 		if let Some(selection) = self.selection {
 			let selection_rect = Rect::new(
-				self.rect.left()
-					+ 5 + text_drawer.text_size(&self.style.text_style, &self.content[..selection.0]).0 as i32,
+				left + self.text_width(text_drawer, &self.content[..selection.0]),
 				self.rect.top() + 5,
-				text_drawer.text_size(&self.style.text_style, &self.content[selection.0..selection.1]).0 as u32,
+				self.text_width(text_drawer, &self.content[selection.0..selection.1]) as u32,
 				self.rect.height() - 10,
 			);
 			let mut selection_color = Colors::LIGHT_BLUE;
@@ -300,5 +631,90 @@ impl Widget for TextInput {
 			fill_rect(canvas, selection_rect, selection_color);
 			canvas.set_blend_mode(BlendMode::None);
 		}
+		canvas.set_clip_rect(None);
+	}
+
+	fn is_hovered(&self) -> bool {
+		self.hovered
+	}
+	fn is_focusable(&self) -> bool {
+		true
+	}
+
+	fn is_focused(&self) -> bool {
+		self.is_focused
+	}
+
+	fn set_focused(&mut self, focused: bool) {
+		self.is_focused = focused;
+		if !focused {
+			self.selection = None;
+		}
+	}
+
+	fn get_rect(&self) -> Option<Rect> {
+		Some(self.rect)
+	}
+
+	fn access_node(&self) -> Option<AccessNode> {
+		// The screen readers only get the bullets of a password
+		let label = self.placeholder.as_deref().unwrap_or_default();
+		Some(AccessNode { value: Some(self.displayed(&self.content)), ..AccessNode::new(Role::TextInput, label) })
+	}
+
+	/// Texts are accepted if the validator accepts them
+	fn accepts_drop(&self, payload: &dyn Any) -> bool {
+		payload
+			.downcast_ref::<String>()
+			.is_some_and(|text| self.validator.as_ref().is_none_or(|validator| validator.is_valid(text)))
+	}
+
+	fn receive_drop(&mut self, payload: &dyn Any) -> bool {
+		match payload.downcast_ref::<String>() {
+			Some(text) => {
+				self.set_content(text.clone());
+				true
+			}
+			None => false,
+		}
+	}
+
+	fn get_cursor(&self) -> Option<MouseCursor> {
+		(self.hovered || self.is_selecting).then_some(MouseCursor::IBeam)
+	}
+
+	fn reset_interaction(&mut self) {
+		self.hovered = false;
+		self.is_selecting = false;
+		self.state = KeyState::Up;
+	}
+
+	fn get_widget_value(&self) -> Option<WidgetValue> {
+		Some(WidgetValue::Text(self.content.clone()))
+	}
+
+	fn get_events(&self) -> Vec<WidgetEvent> {
+		if self.submitted {
+			vec![WidgetEvent::Submitted(self.content.clone())]
+		} else {
+			Vec::new()
+		}
+	}
+
+	fn pull_binding(&mut self) -> bool {
+		let Some(content) = self.binding.as_mut().and_then(|binding| binding.pull()) else {
+			return false;
+		};
+		if content == self.content {
+			return false;
+		}
+		self.set_content(content);
+		true
+	}
+
+	fn push_binding(&mut self) {
+		if let Some(binding) = &mut self.binding {
+			binding.push(&self.content);
+		}
 	}
 }
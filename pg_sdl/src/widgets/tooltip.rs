@@ -0,0 +1,116 @@
+use crate::canvas::{draw_rect, fill_rect};
+use crate::prelude::*;
+
+/// Function drawing the content of a custom tooltip in the given rect
+pub type TooltipDrawer = Box<dyn Fn(&mut Canvas<Window>, &TextDrawer, Rect)>;
+
+/// What a tooltip shows: a (multi-line) text, or anything drawn by a custom function in the given rect
+pub enum TooltipContent {
+	Text(String),
+	Custom { width: u32, height: u32, draw: TooltipDrawer },
+}
+
+pub struct TooltipStyle {
+	background_color: Color,
+	contour_color: Color,
+	text_style: TextStyle,
+}
+
+impl Default for TooltipStyle {
+	fn default() -> Self {
+		Self {
+			background_color: Colors::BEIGE,
+			contour_color: Colors::DARK_GREY,
+			text_style: TextStyle::new(14, None, Color::BLACK, sdl2::ttf::FontStyle::NORMAL),
+		}
+	}
+}
+
+/// A tooltip is shown next to the mouse when a widget is hovered for longer than its **delay** (in seconds).
+///
+/// It is attached to a widget when registering it in `Widgets`, and it's always kept inside the window.
+pub struct Tooltip {
+	content: TooltipContent,
+	delay: f64,
+	style: TooltipStyle,
+}
+
+impl Tooltip {
+	const PADDING: u32 = 4;
+	/// Offset of the tooltip from the mouse position (so that the cursor doesn't cover it)
+	const OFFSET: (i32, i32) = (12, 18);
+	const LINE_SPACING: u32 = 2;
+
+	pub fn new(content: TooltipContent, delay: f64, style: Option<TooltipStyle>) -> Self {
+		Self { content, delay, style: style.unwrap_or_default() }
+	}
+
+	/// A text tooltip, lines are separated by '\n'
+	pub fn text(text: &str, delay: f64) -> Self {
+		Self::new(TooltipContent::Text(text.to_string()), delay, None)
+	}
+
+	/// A tooltip drawn by a custom function, in a rect of the given size
+	pub fn custom(width: u32, height: u32, delay: f64, draw: TooltipDrawer) -> Self {
+		Self::new(TooltipContent::Custom { width, height, draw }, delay, None)
+	}
+
+	pub fn delay(&self) -> f64 {
+		self.delay
+	}
+
+	fn line_height(&self, text_drawer: &TextDrawer, line: &str) -> u32 {
+		if line.is_empty() {
+			self.style.text_style.font_size as u32
+		} else {
+			text_drawer.text_size(&self.style.text_style, line).1
+		}
+	}
+
+	fn content_size(&self, text_drawer: &TextDrawer) -> (u32, u32) {
+		match &self.content {
+			TooltipContent::Text(text) => {
+				let width = text.lines().map(|line| text_drawer.text_size(&self.style.text_style, line).0).max();
+				let height = text.lines().map(|line| self.line_height(text_drawer, line) + Self::LINE_SPACING).sum();
+				(width.unwrap_or(0), height)
+			}
+			TooltipContent::Custom { width, height, .. } => (*width, *height),
+		}
+	}
+
+	/// Draws the tooltip next to the mouse position, moved so that it stays inside the window
+	pub fn draw(&self, canvas: &mut Canvas<Window>, text_drawer: &TextDrawer, mouse_position: Point) {
+		let (width, height) = self.content_size(text_drawer);
+		let (width, height) = (width + 2 * Self::PADDING, height + 2 * Self::PADDING);
+		let (window_width, window_height) = canvas.output_size().unwrap();
+
+		let mut position = mouse_position + point!(Self::OFFSET.0, Self::OFFSET.1);
+		if position.x + width as i32 > window_width as i32 {
+			position.x = window_width as i32 - width as i32;
+		}
+		if position.y + height as i32 > window_height as i32 {
+			// Above the mouse rather than under it
+			position.y = mouse_position.y - height as i32;
+		}
+		let rect = rect!(position.x.max(0), position.y.max(0), width, height);
+
+		fill_rect(canvas, rect, self.style.background_color);
+		draw_rect(canvas, rect, self.style.contour_color);
+
+		let content_position = point!(rect.left() + Self::PADDING as i32, rect.top() + Self::PADDING as i32);
+		match &self.content {
+			TooltipContent::Text(text) => {
+				let mut position = content_position;
+				for line in text.lines() {
+					if !line.is_empty() {
+						text_drawer.draw(canvas, position, &self.style.text_style, line, Align::TopLeft);
+					}
+					position.y += (self.line_height(text_drawer, line) + Self::LINE_SPACING) as i32;
+				}
+			}
+			TooltipContent::Custom { width, height, draw } => {
+				draw(canvas, text_drawer, rect!(content_position.x, content_position.y, *width, *height));
+			}
+		}
+	}
+}